@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::seq::index;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use wrange::ascii::ascii;
+
+mod common;
+
+/// Well-formed art of the given length with `n` endpoints, each stretch
+/// between neighbouring endpoints being all dashes or all spaces
+fn art(rng: &mut ChaCha8Rng, len: usize, n: usize) -> String {
+    let mut positions = index::sample(rng, len, n).into_vec();
+    positions.sort_unstable();
+    let mut chars = vec![' '; len];
+    for (i, &p) in positions.iter().enumerate() {
+        chars[p] = if rng.gen() { 'o' } else { 'x' };
+        let fill = if rng.gen() { '-' } else { ' ' };
+        match positions.get(i + 1) {
+            Some(&next) => chars[p + 1..next].fill(fill),
+            // the last stretch wraps around to the first endpoint
+            None => {
+                chars[p + 1..].fill(fill);
+                chars[..positions[0]].fill(fill);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ascii");
+    let mut rng = common::rng();
+    for (len, n) in [(16, 2), (64, 8), (256, 32)] {
+        let s = art(&mut rng, len, n);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &s, |bench, s| {
+            bench.iter(|| ascii(black_box(s)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Seeded generators shared by the benchmarks, so that every run measures
+//! exactly the same inputs.
+
+#![allow(dead_code)]
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use wrange::{Bound, Wrange, WrangeSet};
+
+pub const SEED: u64 = 0x5eed_a5c5;
+
+pub fn rng() -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(SEED)
+}
+
+fn bound(rng: &mut ChaCha8Rng, x: u32) -> Bound<u32> {
+    if rng.gen() {
+        Bound::Inclusive(x)
+    } else {
+        Bound::Exclusive(x)
+    }
+}
+
+/// An arc starting anywhere on the ring, with a length spread evenly across
+/// orders of magnitude, so that most arcs are short, a few cover much of the
+/// ring, and those running past MAX come out Divergent
+pub fn arc(rng: &mut ChaCha8Rng) -> Wrange<u32> {
+    let start: u32 = rng.gen();
+    let len = 1u32 << rng.gen_range(0..32);
+    let len = rng.gen_range(len / 2..=len);
+    let a = bound(rng, start);
+    let b = bound(rng, start.wrapping_add(len));
+    Wrange::new(a, b)
+}
+
+pub fn arcs(rng: &mut ChaCha8Rng, n: usize) -> Vec<Wrange<u32>> {
+    (0..n).map(|_| arc(rng)).collect()
+}
+
+pub fn set(rng: &mut ChaCha8Rng, n: usize) -> WrangeSet<u32> {
+    arcs(rng, n).into()
+}
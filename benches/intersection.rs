@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use wrange::{Bound, Wrange};
+
+mod common;
+
+/// One range of each variant, with the Convergent and Divergent ones on
+/// either side chosen so that every pairing of them overlaps, and
+/// Convergent with Divergent overlaps in two places
+fn variants(side: usize) -> Vec<(&'static str, Wrange<u32>)> {
+    use Bound::*;
+    let (convergent, divergent) = if side == 0 {
+        (
+            Wrange::new(Inclusive(1_000), Exclusive(4_100_000_000)),
+            Wrange::new(Inclusive(4_000_000_000), Exclusive(5_000)),
+        )
+    } else {
+        (
+            Wrange::new(Inclusive(3_000), Exclusive(9_000)),
+            Wrange::new(Inclusive(3_000_000_000), Exclusive(1_000)),
+        )
+    };
+    vec![
+        ("empty", Wrange::Empty),
+        ("full", Wrange::Full),
+        ("convergent", convergent),
+        ("divergent", divergent),
+    ]
+}
+
+fn intersection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intersection");
+    for (a_name, a) in variants(0) {
+        for (b_name, b) in variants(1) {
+            let name = format!("{}-{}", a_name, b_name);
+            group.bench_function(format!("set/{}", name), |bench| {
+                bench.iter(|| Wrange::intersection(black_box(&a), black_box(&b)))
+            });
+            group.bench_function(format!("pair/{}", name), |bench| {
+                bench.iter(|| Wrange::intersect_pair(black_box(&a), black_box(&b)))
+            });
+        }
+    }
+
+    let mut rng = common::rng();
+    let pairs: Vec<_> = (0..1_000)
+        .map(|_| (common::arc(&mut rng), common::arc(&mut rng)))
+        .collect();
+    group.throughput(Throughput::Elements(pairs.len() as u64));
+    group.bench_function("set/random", |bench| {
+        bench.iter(|| {
+            for (a, b) in &pairs {
+                black_box(Wrange::intersection(a, b));
+            }
+        })
+    });
+    group.bench_function("pair/random", |bench| {
+        bench.iter(|| {
+            for (a, b) in &pairs {
+                black_box(Wrange::intersect_pair(a, b));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, intersection);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use wrange::{Bound, Wrange, WrangeSet};
+
+mod common;
+
+fn normalize(c: &mut Criterion) {
+    use Bound::*;
+    let mut group = c.benchmark_group("normalize");
+
+    let cases = [
+        (
+            "convergent",
+            Wrange::new(Inclusive(1_000u32), Exclusive(5_000)),
+        ),
+        (
+            "divergent",
+            Wrange::new(Inclusive(5_000u32), Exclusive(1_000)),
+        ),
+        ("colocated", Wrange::new(Inclusive(7u32), Exclusive(7))),
+        ("collapsed", Wrange::new(Exclusive(7u32), Exclusive(7))),
+    ];
+    for (name, r) in cases.iter() {
+        group.bench_function(*name, |bench| bench.iter(|| black_box(*r).normalized()));
+    }
+
+    for n in [1, 10, 100, 1_000, 10_000] {
+        let arcs = common::arcs(&mut common::rng(), n);
+        group.bench_with_input(BenchmarkId::new("set", n), &arcs, |bench, arcs| {
+            bench.iter_batched(
+                || WrangeSet::from(arcs.clone()),
+                |s| s.normalized(),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(
+            BenchmarkId::new("canonicalized", n),
+            &arcs,
+            |bench, arcs| {
+                bench.iter_batched(
+                    || WrangeSet::from(arcs.clone()),
+                    |s| s.canonicalized(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, normalize);
+criterion_main!(benches);
@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use wrange::WrangeSet;
+
+mod common;
+
+fn set_ops(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set");
+    let mut rng = common::rng();
+    for n in [1, 10, 100, 1_000, 10_000] {
+        let a = common::set(&mut rng, n);
+        let b = common::set(&mut rng, n);
+        group.bench_with_input(BenchmarkId::new("union", n), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| WrangeSet::union(a, b))
+        });
+        // intersecting every pair and folding the results together is roughly
+        // cubic, taking tens of seconds per iteration from 1,000 ranges up
+        if n <= 100 {
+            group.bench_with_input(
+                BenchmarkId::new("intersection", n),
+                &(&a, &b),
+                |bench, (a, b)| bench.iter(|| WrangeSet::intersection(a, b)),
+            );
+        }
+        let arcs = common::arcs(&mut rng, n);
+        group.bench_with_input(BenchmarkId::new("union_all", n), &arcs, |bench, arcs| {
+            bench.iter(|| WrangeSet::union_all(arcs.iter().copied()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("intersection_all", n),
+            &arcs,
+            |bench, arcs| bench.iter(|| WrangeSet::intersection_all(arcs.iter().copied())),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, set_ops);
+criterion_main!(benches);
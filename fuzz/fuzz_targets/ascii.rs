@@ -0,0 +1,57 @@
+//! Arbitrary strings must never make `try_ascii` panic, and well-formed art
+//! must produce ranges covering exactly the positions drawn as covered: the
+//! dashes and `o`s, and in the extended notation the dashes, `*`s and
+//! inclusive brackets, counted from the domain boundary if one is marked.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wrange::ascii::try_ascii;
+
+fuzz_target!(|s: &str| {
+    let set = match try_ascii(s) {
+        Ok(set) => set,
+        Err(_) => return,
+    };
+    let ranges = set.to_vec();
+    let inclusive = |c| c == '[' || c == ']';
+    // whether each position is drawn as covered, in the order drawn, along
+    // with the number of positions drawn before the domain boundary
+    let mut covered = vec![];
+    let mut boundary = 0;
+    let mut chars = s
+        .to_ascii_lowercase()
+        .chars()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+    while let Some(c) = chars.next() {
+        if c == '|' {
+            // a boundary drawn at both ends is marked by the first
+            if covered.is_empty() || chars.peek().is_some() {
+                boundary = covered.len();
+            }
+            continue;
+        }
+        covered.push(if chars.peek() == Some(&':') {
+            // joined brackets: an end then a start leave no gap, so cover the
+            // position if either does, while a start then an end cover it
+            // only if both do
+            chars.next();
+            let d = chars.next().unwrap();
+            if c == ']' || c == ')' {
+                inclusive(c) || inclusive(d)
+            } else {
+                inclusive(c) && inclusive(d)
+            }
+        } else {
+            matches!(c, '-' | 'o' | '*') || inclusive(c)
+        });
+    }
+    let n = covered.len();
+    for (i, covered) in covered.into_iter().enumerate() {
+        let t = (i + n - boundary) % n;
+        let contains = ranges.iter().any(|r| r.contains(&(t as u8)));
+        assert_eq!(contains, covered, "position {} of |{}|", t, s);
+    }
+});
@@ -0,0 +1,148 @@
+//! Intersections and unions of arbitrary `Wrange<u8>` pairs, checked value by
+//! value against the same operations on bitsets of all 256 values.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use wrange::{Bound, Bounds, Wrange, WrangeSet};
+
+#[derive(Arbitrary, Debug)]
+enum Input {
+    Empty,
+    Full,
+    New(Endpoint, Endpoint),
+    /// A Divergent range with colocated endpoints, which `Wrange::new`
+    /// cannot produce
+    Colocated(Endpoint),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Endpoint {
+    Inclusive(u8),
+    Exclusive(u8),
+}
+
+impl From<Endpoint> for Bound<u8> {
+    fn from(e: Endpoint) -> Self {
+        match e {
+            Endpoint::Inclusive(x) => Bound::Inclusive(x),
+            Endpoint::Exclusive(x) => Bound::Exclusive(x),
+        }
+    }
+}
+
+impl From<Input> for Wrange<u8> {
+    fn from(i: Input) -> Self {
+        match i {
+            Input::Empty => Wrange::Empty,
+            Input::Full => Wrange::Full,
+            Input::New(a, b) => Wrange::new(a.into(), b.into()),
+            Input::Colocated(a) => {
+                let a = Bound::from(a);
+                Wrange::Divergent(Bounds(a, a))
+            }
+        }
+    }
+}
+
+/// The oracle: which of the 256 values a range contains
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Bits([u64; 4]);
+
+impl Bits {
+    const NONE: Self = Bits([0; 4]);
+
+    /// Read straight off the variant and its bounds, rather than through
+    /// `Wrange::contains`, so that the oracle shares no code with the library.
+    /// As documented on `Bounds::normalized`, colocated ends of mixed
+    /// inclusivity stand for two inclusive ends.
+    fn of(r: &Wrange<u8>) -> Self {
+        use Bound::*;
+        let after = |b: &Bound<u8>, x: u8| match *b {
+            Bound::Inclusive(v) => x >= v,
+            Bound::Exclusive(v) => x > v,
+        };
+        let before = |b: &Bound<u8>, x: u8| match *b {
+            Bound::Inclusive(v) => x <= v,
+            Bound::Exclusive(v) => x < v,
+        };
+        let mut bits = Self::NONE;
+        for x in 0..=255u8 {
+            let contains = match r {
+                Wrange::Empty => false,
+                Wrange::Full => true,
+                Wrange::Convergent(Bounds(Inclusive(v), Exclusive(w)))
+                | Wrange::Convergent(Bounds(Exclusive(v), Inclusive(w)))
+                    if v == w =>
+                {
+                    x == *v
+                }
+                Wrange::Convergent(Bounds(a, b)) => after(a, x) && before(b, x),
+                Wrange::Divergent(Bounds(a, b)) => after(a, x) || before(b, x),
+            };
+            if contains {
+                bits.0[x as usize / 64] |= 1 << (x % 64);
+            }
+        }
+        bits
+    }
+
+    fn of_all<'a>(rs: impl IntoIterator<Item = &'a Wrange<u8>>) -> Self {
+        rs.into_iter()
+            .fold(Self::NONE, |acc, r| acc.or(Self::of(r)))
+    }
+
+    fn and(self, other: Self) -> Self {
+        let mut bits = self;
+        for (b, o) in bits.0.iter_mut().zip(other.0.iter()) {
+            *b &= o;
+        }
+        bits
+    }
+
+    fn or(self, other: Self) -> Self {
+        let mut bits = self;
+        for (b, o) in bits.0.iter_mut().zip(other.0.iter()) {
+            *b |= o;
+        }
+        bits
+    }
+
+    fn count(self) -> u128 {
+        self.0.iter().map(|b| b.count_ones() as u128).sum()
+    }
+}
+
+fuzz_target!(|input: (Input, Input)| {
+    let a = Wrange::from(input.0);
+    let b = Wrange::from(input.1);
+    let (bits_a, bits_b) = (Bits::of(&a), Bits::of(&b));
+    assert_eq!(a.measure(), bits_a.count());
+
+    let both = Wrange::intersection(&a, &b).to_vec();
+    assert_eq!(Bits::of_all(&both), bits_a.and(bits_b));
+    assert_eq!(
+        Bits::of_all(&Wrange::intersection(&b, &a).to_vec()),
+        bits_a.and(bits_b)
+    );
+
+    // the pieces of an intersection never overlap, so their measures add up
+    let pair = Wrange::intersect_pair(&a, &b);
+    assert_eq!(Bits::of_all(&pair), bits_a.and(bits_b));
+    assert_eq!(
+        pair.iter().map(|r| r.measure()).sum::<u128>(),
+        bits_a.and(bits_b).count()
+    );
+
+    let either = WrangeSet::union(&a.into(), &b.into());
+    assert_eq!(Bits::of_all(&either.to_vec()), bits_a.or(bits_b));
+
+    // canonical ranges are disjoint, and cover the same values
+    let canonical = either.to_canonical_vec();
+    assert_eq!(Bits::of_all(&canonical), bits_a.or(bits_b));
+    assert_eq!(
+        canonical.iter().map(|r| r.measure()).sum::<u128>(),
+        bits_a.or(bits_b).count()
+    );
+});
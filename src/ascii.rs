@@ -1,21 +1,111 @@
 //! Allows easy construction of small ranges via ASCII art, useful for testing
+//!
+//! The `ascii!` macro of the companion `wrange-macros` crate reads the same
+//! art at compile time, so that malformed fixtures fail the build.
 
 use crate::*;
 
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 use itertools::Itertools;
 
-/// Says whether a substring is all dashes or all spaces. Panics if neither.
-fn dashes(full: &str, begin: usize, end: usize) -> bool {
+/// Why a string could not be read as ASCII art
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsciiError {
+    /// A character the notation does not allow, at this byte offset: the plain
+    /// notation allows only `o`, `x`, `-` and space, and the extended one only
+    /// `[`, `]`, `(`, `)`, `*`, `|`, `:`, `-` and space
+    InvalidChar(usize),
+    /// The string has this many positions, more than there are `u8` values
+    TooLong(usize),
+    /// The stretch between two neighbouring endpoints mixes dashes and spaces
+    MixedStretch { begin: usize, end: usize },
+    /// The stretches at the beginning and end of the string, which are two
+    /// halves of one wrapping stretch, are not both dashes or both spaces
+    MismatchedWrap,
+    /// The bracket at this byte offset follows another start, or another end,
+    /// rather than alternating with it
+    Unpaired(usize),
+    /// The `:` at this byte offset is not between two brackets
+    StrayJoin(usize),
+    /// The dash, space or `*` at this byte offset is on the wrong side of the
+    /// brackets around it
+    Misplaced(usize),
+    /// The `|` at this byte offset marks a second boundary of the domain,
+    /// which has only one
+    ExtraBoundary(usize),
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiError::InvalidChar(i) => write!(f, "Invalid character at position {}", i),
+            AsciiError::TooLong(n) => write!(f, "Too long: {} positions, at most 256", n),
+            AsciiError::MixedStretch { begin, end } => write!(
+                f,
+                "Must have all spaces or all dashes between positions {} and {}",
+                begin, end
+            ),
+            AsciiError::MismatchedWrap => write!(
+                f,
+                "The beginning and end of the string must match with respect to dashes and spaces"
+            ),
+            AsciiError::Unpaired(i) => write!(
+                f,
+                "Starts and ends must alternate, but not at position {}",
+                i
+            ),
+            AsciiError::StrayJoin(i) => {
+                write!(f, "Must have a bracket on each side of position {}", i)
+            }
+            AsciiError::Misplaced(i) => write!(
+                f,
+                "Must have a dash inside a range and a space or star outside one, at position {}",
+                i
+            ),
+            AsciiError::ExtraBoundary(i) => write!(
+                f,
+                "Must mark the domain boundary at most once, but not at position {}",
+                i
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AsciiError {}
+
+impl AsciiError {
+    /// The same error, at the byte offsets `f` gives for its own
+    fn moved(self, f: impl Fn(usize) -> usize) -> Self {
+        use AsciiError::*;
+        match self {
+            InvalidChar(i) => InvalidChar(f(i)),
+            MixedStretch { begin, end } => MixedStretch {
+                begin: f(begin),
+                end: f(end),
+            },
+            Unpaired(i) => Unpaired(f(i)),
+            StrayJoin(i) => StrayJoin(f(i)),
+            Misplaced(i) => Misplaced(f(i)),
+            ExtraBoundary(i) => ExtraBoundary(f(i)),
+            e @ (TooLong(_) | MismatchedWrap) => e,
+        }
+    }
+}
+
+/// Says whether a substring is all dashes or all spaces, or neither.
+fn dashes(full: &str, begin: usize, end: usize) -> Result<bool, AsciiError> {
     let s = &full[begin..end];
     if s.chars().all(|c| c == '-') {
-        true
+        Ok(true)
     } else if s.chars().all(|c| c == ' ') {
-        false
+        Ok(false)
     } else {
-        panic!(
-            "Malformed ascii. Must have all spaces or all dashes between positions {} and {}: |{}|",
-            begin, end, full
-        );
+        Err(AsciiError::MixedStretch { begin, end })
     }
 }
 
@@ -32,8 +122,50 @@ fn bound((t, c): Endpoint) -> Bound<u8> {
 
 type Endpoint<'a> = (usize, &'a str);
 
+/// Read ASCII art as a set of ranges over the positions of the string.
+///
+/// Panics if the art is malformed. See [`try_ascii`].
 pub fn ascii(s: &str) -> WrangeSet<u8> {
-    let s = s.to_lowercase();
+    try_ascii(s).unwrap_or_else(|e| panic!("Malformed ascii. {}: |{}|", e, s))
+}
+
+/// Read ASCII art as a set of ranges over the positions of the string,
+/// or say why it is malformed.
+///
+/// Each `o` is an inclusive endpoint and each `x` an exclusive one, in either
+/// case. The stretch between two neighbouring endpoints is all dashes if it
+/// lies within a range, or all spaces if not.
+///
+/// Art containing any of `[ ] ( ) * | :` is read in the extended notation
+/// instead, in which every range has a diagram of its own:
+///
+/// - `[` and `(` start a range, inclusively or exclusively, and `]` and `)`
+///   end one. Starts and ends must alternate, and the range from a start to
+///   the next end is Divergent if it wraps around the boundary of the domain.
+/// - `*` is a single point, the range [x, x].
+/// - `:` joins the brackets on either side of it into a single position, for
+///   ranges whose endpoints are colocated: `]:[` is a Divergent range with
+///   no gap, and `(:)` a Convergent one with no width.
+/// - `|` marks the boundary of the domain, where it wraps around, and takes
+///   up no position. Position 0 is the first one after it, and the positions
+///   before it come last, so that `  [--|--]  ` is the Divergent range drawn
+///   as `--]    [--`. It may be drawn at both ends of the string, as in
+///   `|--]    [--|`, but not more than once otherwise. Without it, the
+///   boundary lies at the ends of the string.
+/// - As before, every position within a range is a dash, and every other
+///   position a space. With no brackets, a diagram is Full if it is all
+///   dashes, and Empty or a set of points otherwise.
+pub fn try_ascii(s: &str) -> Result<WrangeSet<u8>, AsciiError> {
+    if s.contains(&['[', ']', '(', ')', '*', '|', ':'][..]) {
+        return try_extended(s);
+    }
+    if let Some((i, _)) = s.char_indices().find(|(_, c)| !"oOxX- ".contains(*c)) {
+        return Err(AsciiError::InvalidChar(i));
+    }
+    if s.len() > 256 {
+        return Err(AsciiError::TooLong(s.len()));
+    }
+    let s = s.to_ascii_lowercase();
     let pat_bound = ['o', 'x'];
 
     let intervals = s
@@ -47,14 +179,14 @@ pub fn ascii(s: &str) -> WrangeSet<u8> {
             let lo = e0.0;
             let hi = e1.0;
             let on = if lo < hi {
-                dashes(&s, lo + 1, hi)
+                dashes(&s, lo + 1, hi)?
             } else {
                 // In this branch, either the endpoints are the same, or they are wrapping.
                 // If they are the same, then there is only one endpoint in the string,
                 // and then "on" means the entire space is covered,
                 // and "off" means there is a single zero-length range.
-                let dashes_end = dashes(&s, lo + 1, s.len());
-                let dashes_start = dashes(&s, 0, hi);
+                let dashes_end = dashes(&s, lo + 1, s.len())?;
+                let dashes_start = dashes(&s, 0, hi)?;
                 if lo == s.len() - 1 {
                     dashes_start
                 } else if hi == 0 {
@@ -64,12 +196,12 @@ pub fn ascii(s: &str) -> WrangeSet<u8> {
                 } else if !dashes_start && !dashes_end {
                     false
                 } else {
-                    panic!("Malformed ascii. The beginning and end of the string must match with respect to dashes and spaces: |{}|", s);
+                    return Err(AsciiError::MismatchedWrap);
                 }
             };
-            (e0, e1, on)
+            Ok((e0, e1, on))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
     // fold over intervals, creating Wranges out of each one:
     if let Some((_, _, last_on)) = intervals.last().cloned() {
@@ -93,14 +225,192 @@ pub fn ascii(s: &str) -> WrangeSet<u8> {
             },
         );
 
-        wranges.into()
+        Ok(wranges.into())
+    } else if dashes(&s, 0, s.len())? {
+        Ok(vec![Wrange::Full].into())
     } else {
-        if dashes(&s, 0, s.len()) {
-            vec![Wrange::Full].into()
-        } else {
-            vec![Wrange::Empty].into()
+        Ok(vec![Wrange::Empty].into())
+    }
+}
+
+/// A glyph of the extended notation, taking up a single position
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Glyph {
+    /// A dash, or a space
+    Stretch(bool),
+    Point,
+    /// A bracket, which starts a range or else ends one, with a bound of
+    /// this kind
+    Bracket {
+        start: bool,
+        inclusive: bool,
+    },
+}
+
+fn bracket(c: char) -> Option<Glyph> {
+    let (start, inclusive) = match c {
+        '[' => (true, true),
+        '(' => (true, false),
+        ']' => (false, true),
+        ')' => (false, false),
+        _ => return None,
+    };
+    Some(Glyph::Bracket { start, inclusive })
+}
+
+/// Read ASCII art in the extended notation, starting the domain at its
+/// boundary marker if it has one. See [`try_ascii`].
+fn try_extended(s: &str) -> Result<WrangeSet<u8>, AsciiError> {
+    let bars: Vec<usize> = s.match_indices('|').map(|(i, _)| i).collect();
+    match bars[..] {
+        [] => read_extended(s),
+        // the same boundary, drawn at both ends
+        [0, j] if j == s.len() - 1 => read_extended(&s[1..j]).map_err(|e| e.moved(|k| k + 1)),
+        [i] => {
+            let (before, after) = (&s[..i], &s[i + 1..]);
+            let rotated = format!("{}{}", after, before);
+            read_extended(&rotated).map_err(|e| {
+                e.moved(|k| {
+                    if k < after.len() {
+                        k + i + 1
+                    } else {
+                        k - after.len()
+                    }
+                })
+            })
+        }
+        [_, j, ..] => Err(AsciiError::ExtraBoundary(j)),
+    }
+}
+
+/// Read ASCII art in the extended notation, with no boundary marker
+fn read_extended(s: &str) -> Result<WrangeSet<u8>, AsciiError> {
+    // each glyph with its position, and its byte offset for errors
+    let mut glyphs: Vec<(usize, usize, Glyph)> = vec![];
+    let mut chars = s.char_indices().peekable();
+    let mut t = 0;
+    while let Some((i, c)) = chars.next() {
+        let g = match c {
+            '-' | ' ' => Glyph::Stretch(c == '-'),
+            '*' => Glyph::Point,
+            ':' => return Err(AsciiError::StrayJoin(i)),
+            c => bracket(c).ok_or(AsciiError::InvalidChar(i))?,
+        };
+        glyphs.push((t, i, g));
+        if let (Glyph::Bracket { .. }, Some(&(j, ':'))) = (g, chars.peek()) {
+            chars.next();
+            match chars.next().and_then(|(k, c)| Some((k, bracket(c)?))) {
+                Some((k, g)) => glyphs.push((t, k, g)),
+                None => return Err(AsciiError::StrayJoin(j)),
+            }
+        }
+        t += 1;
+    }
+    if t > 256 {
+        return Err(AsciiError::TooLong(t));
+    }
+
+    let brackets: Vec<_> = glyphs
+        .iter()
+        .filter_map(|&(t, i, g)| match g {
+            Glyph::Bracket { start, inclusive } => {
+                let b = if inclusive {
+                    Bound::Inclusive(t as u8)
+                } else {
+                    Bound::Exclusive(t as u8)
+                };
+                Some((i, start, b))
+            }
+            _ => None,
+        })
+        .collect();
+
+    // Walk the string once, knowing whether each position lies within a range.
+    // The string begins within one if its last bracket is a start, and with no
+    // brackets at all, the first stretch decides.
+    let mut inside = match brackets.last() {
+        Some(&(_, start, _)) => start,
+        None => glyphs.iter().any(|&(_, _, g)| g == Glyph::Stretch(true)),
+    };
+    let mut wranges = vec![];
+    for &(t, i, g) in &glyphs {
+        match g {
+            Glyph::Stretch(dash) if dash != inside => return Err(AsciiError::Misplaced(i)),
+            Glyph::Point if inside => return Err(AsciiError::Misplaced(i)),
+            Glyph::Point => wranges.push(Wrange::new_inclusive(t as u8, t as u8)),
+            Glyph::Bracket { start, .. } if start == inside => return Err(AsciiError::Unpaired(i)),
+            Glyph::Bracket { start, .. } => inside = start,
+            Glyph::Stretch(_) => {}
         }
     }
+
+    // pair each start with the next end, which comes before it if the range wraps
+    for (k, &(_, start, a)) in brackets.iter().enumerate() {
+        if start {
+            let (_, _, b) = brackets[(k + 1) % brackets.len()];
+            wranges.push(if k + 1 < brackets.len() {
+                Wrange::Convergent(Bounds(a, b))
+            } else {
+                Wrange::Divergent(Bounds(a, b))
+            });
+        }
+    }
+
+    if !wranges.is_empty() {
+        Ok(wranges.into())
+    } else if inside {
+        Ok(vec![Wrange::Full].into())
+    } else {
+        Ok(vec![Wrange::Empty].into())
+    }
+}
+
+/// Draw a single range over the positions `0..len` in the extended notation,
+/// so that [`try_ascii`] reads it back as exactly this range.
+///
+/// The range must be well-formed, with a Convergent range's start coming no
+/// later than its end, and a Divergent range's start no earlier.
+///
+/// Panics if an endpoint lies beyond the diagram.
+pub fn diagram(w: &Wrange<u8>, len: usize) -> String {
+    let (a, b, divergent) = match w {
+        Wrange::Empty => return " ".repeat(len),
+        Wrange::Full => return "-".repeat(len),
+        Wrange::Convergent(Bounds(a, b)) => (a, b, false),
+        Wrange::Divergent(Bounds(a, b)) => (a, b, true),
+    };
+    let (at, bt) = (*a.inner() as usize, *b.inner() as usize);
+    assert!(
+        at < len && bt < len,
+        "cannot draw {:?} over {} positions",
+        w,
+        len
+    );
+    let (a_in, b_in) = (
+        matches!(a, Bound::Inclusive(_)),
+        matches!(b, Bound::Inclusive(_)),
+    );
+    let start = if a_in { "[" } else { "(" };
+    let end = if b_in { "]" } else { ")" };
+    (0..len)
+        .map(|t| {
+            if t == at && t == bt {
+                match (divergent, a_in && b_in) {
+                    (true, _) => format!("{}:{}", end, start),
+                    (false, true) => "*".to_string(),
+                    (false, false) => format!("{}:{}", start, end),
+                }
+            } else if t == at {
+                start.to_string()
+            } else if t == bt {
+                end.to_string()
+            } else if (at < t && t < bt) || (divergent && (at < t || t < bt)) {
+                "-".to_string()
+            } else {
+                " ".to_string()
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -216,4 +526,161 @@ mod tests {
         assert_eq!(ascii("o      o").to_vec()[0], Wrange::new_inclusive(7, 0));
         assert_eq!(ascii("x      x").to_vec()[0], Wrange::new_exclusive(7, 0));
     }
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            try_ascii("o-- -o"),
+            Err(AsciiError::MixedStretch { begin: 1, end: 5 })
+        );
+        assert_eq!(try_ascii("-o  o "), Err(AsciiError::MismatchedWrap));
+        assert_eq!(try_ascii("o--y"), Err(AsciiError::InvalidChar(3)));
+        assert_eq!(try_ascii("o-é-o"), Err(AsciiError::InvalidChar(2)));
+        assert_eq!(try_ascii(&"-".repeat(257)), Err(AsciiError::TooLong(257)));
+        assert_eq!(try_ascii("O--X"), Ok(ascii("o--x")));
+
+        assert_eq!(try_ascii("[--[  ]"), Err(AsciiError::Unpaired(3)));
+        assert_eq!(try_ascii("[--]  ]"), Err(AsciiError::Unpaired(6)));
+        assert_eq!(
+            try_ascii("[-]"),
+            Ok(vec![Wrange::new_inclusive(0, 2)].into())
+        );
+        assert_eq!(try_ascii("[- ]"), Err(AsciiError::Misplaced(2)));
+        assert_eq!(try_ascii("-[--]"), Err(AsciiError::Misplaced(0)));
+        assert_eq!(try_ascii("[-*-]"), Err(AsciiError::Misplaced(2)));
+        assert_eq!(try_ascii("--*--"), Err(AsciiError::Misplaced(2)));
+        assert_eq!(try_ascii("[--]:"), Err(AsciiError::StrayJoin(4)));
+        assert_eq!(try_ascii("[--]: "), Err(AsciiError::StrayJoin(4)));
+        assert_eq!(try_ascii(":[--]"), Err(AsciiError::StrayJoin(0)));
+        assert_eq!(try_ascii("[-o-]"), Err(AsciiError::InvalidChar(2)));
+        assert_eq!(
+            try_ascii(&format!("[{}]", "-".repeat(255))),
+            Err(AsciiError::TooLong(257))
+        );
+    }
+
+    #[test]
+    fn extended() {
+        use Bound::*;
+
+        assert_eq!(ascii("(------)"), vec![Wrange::new_exclusive(0, 7)].into());
+        assert_eq!(ascii(")      ("), vec![Wrange::new_exclusive(7, 0)].into());
+        assert_eq!(ascii("--]  [--"), vec![Wrange::new_inclusive(5, 2)].into());
+        assert_eq!(
+            ascii("  [--)  "),
+            vec![Wrange::new(Inclusive(2), Exclusive(5))].into()
+        );
+        assert_eq!(
+            ascii("[-)(--] * "),
+            vec![
+                Wrange::new(Inclusive(0), Exclusive(2)),
+                Wrange::new(Exclusive(3), Inclusive(6)),
+                Wrange::new_inclusive(8, 8),
+            ]
+            .into()
+        );
+        assert_eq!(ascii(" * "), vec![Wrange::new_inclusive(1, 1)].into());
+    }
+
+    #[test]
+    fn extended_joins() {
+        use Bound::*;
+
+        // the end and start of a Divergent range with no gap share a position
+        assert_eq!(
+            ascii("---):(----"),
+            vec![Wrange::Divergent(Bounds(Exclusive(3), Exclusive(3)))].into()
+        );
+        assert_eq!(
+            ascii("---]:(----"),
+            vec![Wrange::Divergent(Bounds(Exclusive(3), Inclusive(3)))].into()
+        );
+        assert_eq!(
+            ascii("]:[---------"),
+            vec![Wrange::Divergent(Bounds(Inclusive(0), Inclusive(0)))].into()
+        );
+        assert_eq!(
+            ascii("---------]:["),
+            vec![Wrange::Divergent(Bounds(Inclusive(9), Inclusive(9)))].into()
+        );
+        // as do the start and end of a Convergent range with no width
+        assert_eq!(
+            ascii("   [:)    "),
+            vec![Wrange::Convergent(Bounds(Inclusive(3), Exclusive(3)))].into()
+        );
+        assert_eq!(ascii("   [:]    "), ascii("   *      "));
+        // two ranges meeting at a position
+        assert_eq!(
+            ascii("[--):[--]"),
+            vec![
+                Wrange::new(Inclusive(0), Exclusive(3)),
+                Wrange::new_inclusive(3, 6),
+            ]
+            .into()
+        );
+        // the old notation's ambiguous `x------x` can be drawn either way
+        assert_eq!(ascii("(------)"), vec![Wrange::new_exclusive(0, 7)].into());
+        assert_eq!(
+            ascii("):(------):("),
+            vec![
+                Wrange::new_exclusive(0, 7),
+                Wrange::Divergent(Bounds(Exclusive(7), Exclusive(0))),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn extended_boundary() {
+        use Bound::*;
+
+        // the domain starts just after the boundary, and wraps around to it
+        assert_eq!(ascii("  [--|--]  "), ascii("--]    [--"));
+        assert_eq!(
+            ascii("  [--|--]  "),
+            vec![Wrange::new_inclusive(7, 2)].into()
+        );
+        assert_eq!(ascii("|--]    [--|"), ascii("--]    [--"));
+        assert_eq!(ascii("|  [--]    "), ascii("  [--]    "));
+        assert_eq!(ascii("  [--]    |"), ascii("  [--]    "));
+        // a range ending just before the boundary and starting just after it
+        assert_eq!(ascii("---)|(---"), vec![Wrange::new_exclusive(0, 7)].into());
+        assert_eq!(
+            ascii("-):(-|----"),
+            vec![Wrange::Divergent(Bounds(Exclusive(5), Exclusive(5)))].into()
+        );
+        assert_eq!(ascii("--|--"), vec![Wrange::Full].into());
+        assert_eq!(ascii("|"), vec![Wrange::Empty].into());
+
+        assert_eq!(try_ascii("[-|-]|"), Err(AsciiError::ExtraBoundary(5)));
+        assert_eq!(try_ascii("|[--]||"), Err(AsciiError::ExtraBoundary(5)));
+        // errors point into the string as drawn
+        assert_eq!(try_ascii("[- |--]"), Err(AsciiError::Misplaced(2)));
+        assert_eq!(try_ascii("  [-|-]]"), Err(AsciiError::Unpaired(7)));
+        assert_eq!(try_ascii("|[-o-]|"), Err(AsciiError::InvalidChar(3)));
+    }
+
+    #[test]
+    fn draw() {
+        use Bound::*;
+
+        assert_eq!(diagram(&Wrange::Empty, 4), "    ");
+        assert_eq!(diagram(&Wrange::Full, 4), "----");
+        assert_eq!(diagram(&Wrange::new_inclusive(1, 3), 6), " [-]  ");
+        assert_eq!(diagram(&Wrange::new_exclusive(4, 1), 6), "-)  (-");
+        assert_eq!(diagram(&Wrange::new_inclusive(2, 2), 6), "  *   ");
+        assert_eq!(
+            diagram(&Wrange::new(Exclusive(2), Inclusive(2)), 6),
+            "  (:]   "
+        );
+        assert_eq!(
+            diagram(&Wrange::Divergent(Bounds(Exclusive(2), Exclusive(2))), 6),
+            "--):(---"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn draw_beyond() {
+        diagram(&Wrange::new_inclusive(1, 6), 6);
+    }
 }
@@ -1,6 +1,6 @@
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
-#[derive(Debug, Copy, Clone, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub enum Bound<T> {
     Exclusive(T),
     Inclusive(T),
@@ -8,9 +8,9 @@ pub enum Bound<T> {
 
 impl<T> PartialOrd for Bound<T>
 where
-    T: PartialOrd + Clone,
+    T: PartialOrd,
 {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         use Bound::*;
         match self.inner().partial_cmp(other.inner()) {
             Some(Ordering::Equal) => match (self, other) {
@@ -24,20 +24,24 @@ where
     }
 }
 
-impl<T> PartialEq for Bound<T>
-where
-    T: PartialEq + Clone,
-{
-    fn eq(&self, other: &Self) -> bool {
-        use Bound::*;
-        match (self, other) {
-            (Exclusive(x), Exclusive(y)) | (Inclusive(x), Inclusive(y)) => x == y,
-            _ => false,
+impl<T> Eq for Bound<T> where T: Eq {}
+
+impl<T> Bound<T> {
+    /// Apply a function to the inner value, keeping the inclusivity
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Bound<U> {
+        match self {
+            Bound::Exclusive(t) => Bound::Exclusive(f(t)),
+            Bound::Inclusive(t) => Bound::Inclusive(f(t)),
         }
     }
-}
 
-impl<T> Eq for Bound<T> where T: PartialEq + Clone {}
+    pub(crate) fn inner(&self) -> &T {
+        match self {
+            Bound::Inclusive(ref t) => t,
+            Bound::Exclusive(ref t) => t,
+        }
+    }
+}
 
 impl<T> Bound<T>
 where
@@ -55,7 +59,7 @@ where
         } else {
             b
         }
-        .to_owned()
+        .clone()
     }
 
     pub fn intersection_max(a: &Self, b: &Self) -> Self {
@@ -70,7 +74,7 @@ where
         } else {
             b
         }
-        .to_owned()
+        .clone()
     }
 
     pub fn union_min(a: &Self, b: &Self) -> Self {
@@ -85,7 +89,7 @@ where
         } else {
             b
         }
-        .to_owned()
+        .clone()
     }
 
     pub fn union_max(a: &Self, b: &Self) -> Self {
@@ -100,29 +104,25 @@ where
         } else {
             b
         }
-        .to_owned()
+        .clone()
     }
+}
 
+impl<T> Bound<T>
+where
+    T: PartialEq,
+{
     pub fn overlaps(&self, other: &Self) -> bool {
         self.inner() == other.inner()
     }
-
-    pub(crate) fn inner(&self) -> &T {
-        match self {
-            Bound::Inclusive(ref t) => t,
-            Bound::Exclusive(ref t) => t,
-        }
-    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, derive_more::Constructor)]
-pub struct Bounds<T>(pub Bound<T>, pub Bound<T>)
-where
-    T: Clone + PartialEq + PartialOrd;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, derive_more::Constructor)]
+pub struct Bounds<T>(pub Bound<T>, pub Bound<T>);
 
 impl<T> Bounds<T>
 where
-    T: Clone + PartialEq + PartialOrd,
+    T: Clone + PartialEq,
 {
     /// Perform some sensible normalization:
     /// Two overlapping (colocated) endpoints with both inclusive and exclusive
@@ -151,11 +151,12 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::cmp::Ordering;
+    use core::cmp::Ordering;
 
     use super::*;
 
     #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
     fn test_order() {
         use Bound::*;
 
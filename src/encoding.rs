@@ -0,0 +1,558 @@
+//! A compact, versioned binary encoding for Wranges and WrangeSets over
+//! integer domains.
+//!
+//! Every encoding begins with a version byte. A single Wrange is then
+//! encoded as one range record; a WrangeSet is encoded as a varint count
+//! followed by the records of its canonical ranges in order.
+//!
+//! A range record is a tag byte giving the variant, followed, for Convergent
+//! and Divergent ranges, by two varints. The first varint is the distance up the ring from the end of the previous
+//! range in the set (or from MIN) to the start of this range, and the second
+//! is the distance up the ring from the start to the end.
+//!
+//! Varints are unsigned LEB128, and values are encoded as offsets from the MIN
+//! of their domain, so signed types take no more space than unsigned ones.
+//!
+//! Ranges and sets are encoded by the values they contain, as their runs of
+//! consecutive values in the sense of the [fingerprint](crate::fingerprint)
+//! module: each run is one range, inclusive of its first and last value, or
+//! Full if it covers the whole domain. So everything containing the same
+//! values has exactly one encoding, and decoding rejects anything else. As
+//! the bounds of a run are always inclusive, records do not store them.
+//!
+//! Round-tripping therefore gives back the canonical form rather than the
+//! original: a range with the same values between inclusive bounds, and for
+//! a set, its runs in order, as in `[10, 20)` decoding as `[10, 19]`.
+
+use crate::{Bound, Bounds, Wrange, WrangeSet, WrappingInteger};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+/// The current version of the encoding
+pub const VERSION: u8 = 1;
+
+const TAG_EMPTY: u8 = 0;
+const TAG_CONVERGENT: u8 = 1;
+const TAG_DIVERGENT: u8 = 2;
+const TAG_FULL: u8 = 3;
+const TAG_VARIANT_MASK: u8 = 0b0011;
+
+/// The ways in which decoding can fail
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input ended partway through a value
+    UnexpectedEnd,
+    /// The version byte is not one this crate knows how to decode
+    UnsupportedVersion(u8),
+    /// A tag byte has bits set beyond those giving the variant
+    InvalidTag(u8),
+    /// A varint is longer than necessary, or too large to be represented
+    InvalidVarint,
+    /// A decoded value lies outside of the domain
+    OutOfRange,
+    /// The input describes a valid value, but not in the one form which
+    /// encoding would produce
+    NonCanonical,
+    /// The input continues after the end of the encoded value
+    TrailingBytes,
+    /// Reading the input failed
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::UnsupportedVersion(v) => write!(f, "unsupported encoding version {}", v),
+            DecodeError::InvalidTag(t) => write!(f, "invalid tag byte {:#04x}", t),
+            DecodeError::InvalidVarint => write!(f, "overlong or oversized varint"),
+            DecodeError::OutOfRange => write!(f, "value out of range for the domain"),
+            DecodeError::NonCanonical => write!(f, "value is not in canonical form"),
+            DecodeError::TrailingBytes => write!(f, "unexpected bytes after the end of the value"),
+            #[cfg(feature = "std")]
+            DecodeError::Io(e) => write!(f, "failed to read input: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for DecodeError {
+    fn from(e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            DecodeError::UnexpectedEnd
+        } else {
+            DecodeError::Io(e)
+        }
+    }
+}
+
+/// A source of bytes to decode from
+trait Source {
+    fn next_byte(&mut self) -> Result<u8, DecodeError>;
+}
+
+impl Source for core::slice::Iter<'_, u8> {
+    fn next_byte(&mut self) -> Result<u8, DecodeError> {
+        self.next().copied().ok_or(DecodeError::UnexpectedEnd)
+    }
+}
+
+#[cfg(feature = "std")]
+struct Reader<R>(R);
+
+#[cfg(feature = "std")]
+impl<R: Read> Source for Reader<R> {
+    fn next_byte(&mut self) -> Result<u8, DecodeError> {
+        let mut byte = [0];
+        self.0.read_exact(&mut byte)?;
+        Ok(byte[0])
+    }
+}
+
+fn put_varint(buf: &mut Vec<u8>, mut n: u128) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+fn get_varint(src: &mut impl Source) -> Result<u128, DecodeError> {
+    let mut n: u128 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = src.next_byte()?;
+        let bits = (byte & 0x7f) as u128;
+        if shift > 0 && byte == 0 {
+            // a trailing zero group adds nothing, so could have been left off
+            return Err(DecodeError::InvalidVarint);
+        }
+        if shift >= 128 || (shift > 121 && bits >> (128 - shift) != 0) {
+            return Err(DecodeError::InvalidVarint);
+        }
+        n |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+        shift += 7;
+    }
+}
+
+/// The number of steps going up the ring from `from` to `to`
+fn steps_up<T: WrappingInteger>(from: u128, to: T) -> u128 {
+    (to.to_offset() + T::SIZE - from) % T::SIZE
+}
+
+/// Encode one range, which must be a run as given by [`run`], returning the
+/// offset of its end
+fn put_range<T: WrappingInteger>(buf: &mut Vec<u8>, r: &Wrange<T>, prev: u128) -> u128 {
+    match r {
+        Wrange::Empty => {
+            buf.push(TAG_EMPTY);
+            prev
+        }
+        Wrange::Full => {
+            buf.push(TAG_FULL);
+            prev
+        }
+        Wrange::Convergent(Bounds(a, b)) | Wrange::Divergent(Bounds(a, b)) => {
+            let variant = match r {
+                Wrange::Convergent(_) => TAG_CONVERGENT,
+                _ => TAG_DIVERGENT,
+            };
+            buf.push(variant);
+            put_varint(buf, steps_up(prev, *a.inner()));
+            put_varint(buf, steps_up(a.inner().to_offset(), *b.inner()));
+            b.inner().to_offset()
+        }
+    }
+}
+
+/// The one range in which a run of values is encoded, given the offsets of
+/// its first and last values
+fn run<T: WrappingInteger>((first, last): (u128, u128)) -> Wrange<T> {
+    if (last + 1) % T::SIZE == first {
+        Wrange::Full
+    } else {
+        Wrange::new_inclusive(T::from_offset(first), T::from_offset(last))
+    }
+}
+
+/// The one range in which the values of this range are encoded
+fn canonical<T: WrappingInteger>(r: &Wrange<T>) -> Wrange<T> {
+    match r.first_and_last() {
+        Some((first, last)) => run((first.to_offset(), last.to_offset())),
+        None => Wrange::Empty,
+    }
+}
+
+/// The ranges in which the values of this set are encoded, in order
+fn canonical_set<T: WrappingInteger>(set: &WrangeSet<T>) -> Vec<Wrange<T>> {
+    set.runs().into_iter().map(run).collect()
+}
+
+/// Decode one range which follows a range ending at offset `prev`,
+/// returning it along with the offset of its end
+fn get_range<T: WrappingInteger>(
+    src: &mut impl Source,
+    prev: u128,
+) -> Result<(Wrange<T>, u128), DecodeError> {
+    let tag = src.next_byte()?;
+    if tag & !TAG_VARIANT_MASK != 0 {
+        return Err(DecodeError::InvalidTag(tag));
+    }
+    match tag {
+        TAG_EMPTY => Ok((Wrange::Empty, prev)),
+        TAG_FULL => Ok((Wrange::Full, prev)),
+        variant => {
+            let gap = get_varint(src)?;
+            let length = get_varint(src)?;
+            if gap >= T::SIZE || length >= T::SIZE {
+                return Err(DecodeError::OutOfRange);
+            }
+            let start = (prev + gap) % T::SIZE;
+            let end = (start + length) % T::SIZE;
+            let bounds = Bounds(
+                Bound::Inclusive(T::from_offset(start)),
+                Bound::Inclusive(T::from_offset(end)),
+            );
+            let wraps = start + length >= T::SIZE;
+            let r = match variant {
+                TAG_CONVERGENT if !wraps => Wrange::Convergent(bounds),
+                TAG_DIVERGENT if wraps || length == 0 => Wrange::Divergent(bounds),
+                _ => return Err(DecodeError::NonCanonical),
+            };
+            if r.normalized() != r {
+                return Err(DecodeError::NonCanonical);
+            }
+            Ok((r, end))
+        }
+    }
+}
+
+fn get_version(src: &mut impl Source) -> Result<(), DecodeError> {
+    match src.next_byte()? {
+        VERSION => Ok(()),
+        v => Err(DecodeError::UnsupportedVersion(v)),
+    }
+}
+
+fn get_wrange<T: WrappingInteger>(src: &mut impl Source) -> Result<Wrange<T>, DecodeError> {
+    get_version(src)?;
+    let (r, _) = get_range(src, 0)?;
+    if canonical(&r) != r {
+        return Err(DecodeError::NonCanonical);
+    }
+    Ok(r)
+}
+
+fn get_wrange_set<T: WrappingInteger>(src: &mut impl Source) -> Result<WrangeSet<T>, DecodeError> {
+    get_version(src)?;
+    let count = get_varint(src)?;
+    let mut ranges = vec![];
+    let mut prev = 0;
+    for _ in 0..count {
+        let (r, end) = get_range(src, prev)?;
+        ranges.push(r);
+        prev = end;
+    }
+    let set: WrangeSet<T> = ranges.clone().into();
+    if canonical_set(&set) != ranges {
+        return Err(DecodeError::NonCanonical);
+    }
+    Ok(set)
+}
+
+impl<T> Wrange<T>
+where
+    T: WrappingInteger,
+{
+    /// Encode the values of this range in the compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![VERSION];
+        put_range(&mut buf, &canonical(self), 0);
+        buf
+    }
+
+    /// Decode a range from exactly these bytes. This gives the canonical form
+    /// of the range encoded, holding the same values between inclusive bounds.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut src = bytes.iter();
+        let r = get_wrange(&mut src)?;
+        if src.next().is_some() {
+            return Err(DecodeError::TrailingBytes);
+        }
+        Ok(r)
+    }
+
+    /// Write the values of this range in the compact binary format
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Read one range, leaving any further input unread
+    #[cfg(feature = "std")]
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        get_wrange(&mut Reader(reader))
+    }
+}
+
+impl<T> WrangeSet<T>
+where
+    T: WrappingInteger,
+{
+    /// Encode the values of this set in the compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let ranges = canonical_set(self);
+        let mut buf = vec![VERSION];
+        put_varint(&mut buf, ranges.len() as u128);
+        let mut prev = 0;
+        for r in ranges.iter() {
+            prev = put_range(&mut buf, r, prev);
+        }
+        buf
+    }
+
+    /// Decode a set from exactly these bytes. This gives the canonical form
+    /// of the set encoded, as its runs of values in order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut src = bytes.iter();
+        let set = get_wrange_set(&mut src)?;
+        if src.next().is_some() {
+            return Err(DecodeError::TrailingBytes);
+        }
+        Ok(set)
+    }
+
+    /// Write the values of this set in the compact binary format
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Read one set, leaving any further input unread
+    #[cfg(feature = "std")]
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, DecodeError> {
+        get_wrange_set(&mut Reader(reader))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bound::*;
+    use Wrange::*;
+
+    #[test]
+    fn test_varint() {
+        for n in [0, 1, 127, 128, 300, u64::MAX as u128, u128::MAX] {
+            let mut buf = vec![];
+            put_varint(&mut buf, n);
+            assert_eq!(get_varint(&mut buf.iter()).unwrap(), n);
+        }
+        assert!(matches!(
+            get_varint(&mut [0x80, 0x00].iter()),
+            Err(DecodeError::InvalidVarint)
+        ));
+        assert!(matches!(
+            get_varint(&mut [[0xff; 19].as_ref(), &[0x04]].concat().iter()),
+            Err(DecodeError::InvalidVarint)
+        ));
+        assert!(matches!(
+            get_varint(&mut [0x80].iter()),
+            Err(DecodeError::UnexpectedEnd)
+        ));
+    }
+
+    #[test]
+    fn test_wrange_encoding() {
+        assert_eq!(Wrange::<u32>::Empty.to_bytes(), vec![VERSION, 0]);
+        assert_eq!(Wrange::<u32>::Full.to_bytes(), vec![VERSION, 3]);
+        // encoded as the run of values from 10 to 19
+        assert_eq!(
+            Wrange::<u32>::new(Inclusive(10), Exclusive(20)).to_bytes(),
+            vec![VERSION, 1, 10, 9]
+        );
+        assert_eq!(
+            Wrange::<u32>::new(Inclusive(10), Exclusive(20)).to_bytes(),
+            Wrange::<u32>::new_inclusive(10, 19).to_bytes()
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(0, 255).to_bytes(),
+            Wrange::<u8>::Full.to_bytes()
+        );
+        assert_eq!(
+            Wrange::<u8>::new_exclusive(4, 5).to_bytes(),
+            Wrange::<u8>::Empty.to_bytes()
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).to_bytes(),
+            vec![VERSION, 2, 0xfa, 0x01, 11]
+        );
+        // decoded as the same run of values, between inclusive bounds
+        assert_eq!(
+            Wrange::<u32>::from_bytes(&Wrange::<u32>::new(Inclusive(10), Exclusive(20)).to_bytes())
+                .unwrap(),
+            Wrange::new_inclusive(10, 19)
+        );
+        // normalized before encoding
+        assert_eq!(
+            Divergent(Bounds(Inclusive(3u8), Inclusive(3))).to_bytes(),
+            Wrange::<u8>::Full.to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_wrange_set_encoding() {
+        let set: WrangeSet<u32> = vec![
+            Wrange::new_inclusive(1000, 1010),
+            Wrange::new_inclusive(1005, 1020),
+            Wrange::new_inclusive(1100, 1200),
+            Empty,
+        ]
+        .into();
+        assert_eq!(
+            set.to_bytes(),
+            vec![VERSION, 2, 1, 0xe8, 0x07, 20, 1, 80, 100]
+        );
+        assert_eq!(
+            WrangeSet::<u32>::from_bytes(&set.to_bytes()).unwrap(),
+            set.canonicalized()
+        );
+
+        // touching runs are one run
+        let split: WrangeSet<u8> =
+            vec![Wrange::new_inclusive(2, 5), Wrange::new_inclusive(6, 9)].into();
+        assert_eq!(
+            split.to_bytes(),
+            WrangeSet::from(Wrange::new_inclusive(2u8, 9)).to_bytes()
+        );
+        let halves: WrangeSet<u8> = vec![
+            Wrange::new_inclusive(0, 99),
+            Wrange::new_inclusive(100, 255),
+        ]
+        .into();
+        assert_eq!(halves.to_bytes(), vec![VERSION, 1, 3]);
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let err = |bytes: &[u8]| Wrange::<u8>::from_bytes(bytes).unwrap_err();
+
+        assert!(matches!(err(&[]), DecodeError::UnexpectedEnd));
+        assert!(matches!(err(&[2, 0]), DecodeError::UnsupportedVersion(2)));
+        assert!(matches!(
+            err(&[VERSION, 0b0100]),
+            DecodeError::InvalidTag(_)
+        ));
+        assert!(matches!(
+            err(&[VERSION, 0b10001]),
+            DecodeError::InvalidTag(_)
+        ));
+        // there are no inclusivity bits, as every bound is inclusive
+        assert!(matches!(
+            err(&[VERSION, 0b1101, 10, 9]),
+            DecodeError::InvalidTag(_)
+        ));
+        assert!(matches!(err(&[VERSION, 1, 10]), DecodeError::UnexpectedEnd));
+        assert!(matches!(err(&[VERSION, 0, 0]), DecodeError::TrailingBytes));
+        assert!(matches!(
+            err(&[VERSION, 1, 0x80, 0x02, 0]),
+            DecodeError::OutOfRange
+        ));
+        assert!(matches!(
+            err(&[VERSION, 1, 0x8a, 0x00, 0]),
+            DecodeError::InvalidVarint
+        ));
+
+        // a wrapping range tagged as Convergent, and vice versa
+        assert!(matches!(
+            err(&[VERSION, 1, 0xfa, 0x01, 11]),
+            DecodeError::NonCanonical
+        ));
+        assert!(matches!(
+            err(&[VERSION, 2, 10, 11]),
+            DecodeError::NonCanonical
+        ));
+        // not normalized
+        assert!(matches!(
+            err(&[VERSION, 2, 10, 0]),
+            DecodeError::NonCanonical
+        ));
+        // [0, 255] rather than Full
+        assert!(matches!(
+            err(&[VERSION, 1, 0, 0xff, 0x01]),
+            DecodeError::NonCanonical
+        ));
+
+        let set_err = |bytes: &[u8]| WrangeSet::<u8>::from_bytes(bytes).unwrap_err();
+        // overlapping, touching, out of order, or containing Empty
+        assert!(matches!(
+            set_err(&[VERSION, 2, 1, 1, 5, 1, 0, 5]),
+            DecodeError::NonCanonical
+        ));
+        assert!(matches!(
+            set_err(&[VERSION, 2, 1, 1, 5, 1, 1, 5]),
+            DecodeError::NonCanonical
+        ));
+        assert!(matches!(
+            set_err(&[VERSION, 2, 1, 20, 5, 1, 0xfa, 0x01, 5]),
+            DecodeError::NonCanonical
+        ));
+        assert!(matches!(
+            set_err(&[VERSION, 1, 0]),
+            DecodeError::NonCanonical
+        ));
+        assert!(matches!(
+            set_err(&[VERSION, 2, 3, 3]),
+            DecodeError::NonCanonical
+        ));
+        // [2, 5] and [6, 9] rather than [2, 9]
+        assert!(matches!(
+            set_err(&[VERSION, 2, 1, 2, 3, 1, 1, 3]),
+            DecodeError::NonCanonical
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_write() {
+        let a = Wrange::<u16>::new_inclusive(60000, 7);
+        let b: WrangeSet<u16> = vec![
+            Wrange::new_exclusive(1, 2000),
+            Wrange::new_inclusive(300, 30000),
+        ]
+        .into();
+
+        let mut buf = vec![];
+        a.write_to(&mut buf).unwrap();
+        b.write_to(&mut buf).unwrap();
+        a.write_to(&mut buf).unwrap();
+
+        let mut reader = &buf[..];
+        assert_eq!(Wrange::read_from(&mut reader).unwrap(), a);
+        assert_eq!(
+            WrangeSet::<u16>::read_from(&mut reader).unwrap(),
+            Wrange::new_inclusive(2, 30000).into()
+        );
+        assert_eq!(Wrange::read_from(&mut reader).unwrap(), a);
+        assert!(matches!(
+            Wrange::<u16>::read_from(&mut reader),
+            Err(DecodeError::UnexpectedEnd)
+        ));
+    }
+}
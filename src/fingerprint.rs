@@ -0,0 +1,161 @@
+//! A stable content hash of a WrangeSet over an integer domain.
+//!
+//! Two sets which contain exactly the same values always have the same
+//! fingerprint, however their members happen to be split up or written, and
+//! the bytes fed to the hasher are fixed: they do not depend on the platform,
+//! on iteration order, or on the version of this crate.
+//!
+//! The set is first reduced to its canonical runs: maximal sequences of
+//! consecutive values going up the ring, in order of their first value, where
+//! a run may wrap from MAX around to MIN. The number of runs is then written as
+//! a little-endian u64, followed by the offsets from MIN of the first and last
+//! value of each run, each as a little-endian u128. A full set is a single run
+//! from MIN to MAX.
+
+use crate::{WrangeSet, WrappingInteger};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+/// The 64-bit FNV-1a hash, used for [`WrangeSet::fingerprint`]
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<T> WrangeSet<T>
+where
+    T: WrappingInteger,
+{
+    /// The canonical runs of this set, as the offsets of their first and last
+    /// values. A run which wraps around has its first offset above its last.
+    pub(crate) fn runs(&self) -> Vec<(u128, u128)> {
+        let whole = (0, T::SIZE - 1);
+        let mut runs = vec![];
+        for r in self.to_vec() {
+            if r.measure() == T::SIZE {
+                return vec![whole];
+            }
+            if let Some((first, last)) = r.first_and_last() {
+                let (first, last) = (first.to_offset(), last.to_offset());
+                if first <= last {
+                    runs.push((first, last));
+                } else {
+                    runs.push((0, last));
+                    runs.push((first, T::SIZE - 1));
+                }
+            }
+        }
+        runs.sort_unstable();
+
+        let mut merged: Vec<(u128, u128)> = Vec::with_capacity(runs.len());
+        for (first, last) in runs {
+            match merged.last_mut() {
+                Some(prev) if first <= prev.1 + 1 => prev.1 = prev.1.max(last),
+                _ => merged.push((first, last)),
+            }
+        }
+
+        // join a run ending at MAX onto one starting at MIN
+        if merged.len() > 1 && merged[0].0 == 0 && merged[merged.len() - 1].1 == whole.1 {
+            let (_, last) = merged.remove(0);
+            if let Some(run) = merged.last_mut() {
+                run.1 = last;
+            }
+        }
+        merged
+    }
+
+    /// A 64-bit fingerprint of the values this set contains, computed with
+    /// FNV-1a. See the [module docs](crate::fingerprint) for the guarantees.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.fingerprint_with(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Feed the canonical runs of this set into any hasher. The result is as
+    /// stable as the hasher itself: note that `std`'s `DefaultHasher` is
+    /// *not* guaranteed to be stable between Rust releases.
+    pub fn fingerprint_with<H: Hasher>(&self, hasher: &mut H) {
+        let runs = self.runs();
+        hasher.write(&(runs.len() as u64).to_le_bytes());
+        for (first, last) in runs {
+            hasher.write(&first.to_le_bytes());
+            hasher.write(&last.to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bound::*, Wrange};
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // these values must never change
+        assert_eq!(
+            WrangeSet::<u8>::from(vec![]).fingerprint(),
+            0xa8c7_f832_281a_39c5
+        );
+        assert_eq!(
+            WrangeSet::<u8>::from(vec![Wrange::Full]).fingerprint(),
+            0x1bc6_5f35_0d1f_d71b
+        );
+        assert_eq!(
+            WrangeSet::from(vec![Wrange::new(Inclusive(10u32), Exclusive(20))]).fingerprint(),
+            0x7894_d8fd_f9fe_b27d
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_representation() {
+        let a: WrangeSet<u8> = vec![
+            Wrange::new_inclusive(10, 20),
+            Wrange::new_inclusive(15, 30),
+            Wrange::new_inclusive(250, 5),
+            Wrange::Empty,
+        ]
+        .into();
+        let b: WrangeSet<u8> = vec![
+            Wrange::new(Inclusive(2), Inclusive(5)),
+            Wrange::new_inclusive(10, 30),
+            Wrange::new(Inclusive(250), Exclusive(2)),
+        ]
+        .into();
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_eq!(
+            WrangeSet::<u8>::from(vec![Wrange::Empty]).fingerprint(),
+            WrangeSet::<u8>::from(vec![]).fingerprint()
+        );
+
+        assert_eq!(
+            WrangeSet::<u8>::from(vec![Wrange::new_inclusive(0, 255)]).fingerprint(),
+            WrangeSet::<u8>::from(vec![Wrange::Full]).fingerprint()
+        );
+        assert_eq!(
+            WrangeSet::<u8>::from(vec![Wrange::new_exclusive(3, 7)]).fingerprint(),
+            WrangeSet::<u8>::from(vec![Wrange::new_inclusive(4, 6)]).fingerprint()
+        );
+
+        let c: WrangeSet<u8> = vec![Wrange::new_inclusive(10, 31)].into();
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+}
@@ -0,0 +1,287 @@
+//! Ranges over floating-point values, such as angles and phases.
+//!
+//! Floats are not `Ord` or `Hash`, so they are wrapped in [`TotalFloat`],
+//! which orders them by IEEE 754 `totalOrder`. An [`Angle`] domain then gives
+//! the ring its period, so that ranges of angles can be measured and bisected.
+
+use crate::{Bounds, Wrange};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Div, Sub};
+use core::str::FromStr;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A primitive floating-point type: `f32` or `f64`
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + private::Sealed
+{
+    const ZERO: Self;
+    const TWO: Self;
+    const TAU: Self;
+    const DEGREES: Self;
+
+    fn total_cmp(&self, other: &Self) -> Ordering;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn is_finite(self) -> bool;
+    fn to_bits(self) -> u64;
+}
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {
+        $(
+            impl Float for $t {
+                const ZERO: Self = 0.0;
+                const TWO: Self = 2.0;
+                const TAU: Self = core::$t::consts::TAU;
+                const DEGREES: Self = 360.0;
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    <$t>::total_cmp(self, other)
+                }
+
+                // `core` has no `rem_euclid` for floats, and only positive
+                // periods are ever used here
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    let r = self % rhs;
+                    if r < 0.0 {
+                        r + rhs
+                    } else {
+                        r
+                    }
+                }
+
+                fn is_finite(self) -> bool {
+                    <$t>::is_finite(self)
+                }
+
+                fn to_bits(self) -> u64 {
+                    <$t>::to_bits(self) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+/// A float with a total order, so that it can be used as the domain of a Wrange.
+///
+/// Values are ordered as by `total_cmp`: in particular `-0.0` comes just
+/// before `0.0`, and they are not equal.
+#[derive(Clone, Copy, Debug)]
+pub struct TotalFloat<F: Float>(pub F);
+
+impl<F: Float> PartialEq for TotalFloat<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for TotalFloat<F> {}
+
+impl<F: Float> PartialOrd for TotalFloat<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for TotalFloat<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl<F: Float> Hash for TotalFloat<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+impl<F: Float> Display for TotalFloat<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<F: Float> FromStr for TotalFloat<F> {
+    type Err = F::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(TotalFloat)
+    }
+}
+
+impl<F: Float> From<F> for TotalFloat<F> {
+    fn from(x: F) -> Self {
+        TotalFloat(x)
+    }
+}
+
+/// A circular domain of angles, `[0, period)`, in which `period` wraps back
+/// around to 0.
+///
+/// Ranges should be built with [`Angle::sector`], so that their endpoints
+/// lie within the domain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Angle<F: Float> {
+    period: F,
+}
+
+impl<F: Float> Angle<F> {
+    /// The domain of angles with the given period.
+    ///
+    /// Panics if the period is not finite and positive.
+    pub fn new(period: F) -> Self {
+        assert!(
+            period.is_finite() && period > F::ZERO,
+            "the period of an angle must be finite and positive"
+        );
+        Self { period }
+    }
+
+    /// Angles in degrees, with a period of 360
+    pub fn degrees() -> Self {
+        Self::new(F::DEGREES)
+    }
+
+    /// Angles in radians, with a period of 2π
+    pub fn radians() -> Self {
+        Self::new(F::TAU)
+    }
+
+    pub fn period(&self) -> F {
+        self.period
+    }
+
+    /// The equivalent angle within `[0, period)`.
+    ///
+    /// Panics if `x` is not finite.
+    pub fn wrap(&self, x: F) -> TotalFloat<F> {
+        assert!(x.is_finite(), "cannot wrap a non-finite angle");
+        // adding zero turns -0.0 into 0.0
+        let r = x.rem_euclid(self.period) + F::ZERO;
+        // rounding can carry a tiny negative angle all the way up to the period
+        TotalFloat(if r >= self.period { F::ZERO } else { r })
+    }
+
+    /// The sector going up from `start` to `end`, both inclusive,
+    /// with each endpoint first wrapped into the domain.
+    pub fn sector(&self, start: F, end: F) -> Wrange<TotalFloat<F>> {
+        Wrange::new_inclusive(self.wrap(start), self.wrap(end))
+    }
+
+    /// Whether the range contains the angle `x`, after wrapping it into the domain
+    pub fn contains(&self, w: &Wrange<TotalFloat<F>>, x: F) -> bool {
+        x.is_finite() && w.contains(&self.wrap(x))
+    }
+
+    /// The angle swept by the range, going up from its start to its end
+    pub fn measure(&self, w: &Wrange<TotalFloat<F>>) -> F {
+        match w.normalized() {
+            Wrange::Empty => F::ZERO,
+            Wrange::Full => self.period,
+            Wrange::Convergent(Bounds(a, b)) => b.inner().0 - a.inner().0,
+            Wrange::Divergent(Bounds(a, b)) => self.period - a.inner().0 + b.inner().0,
+        }
+    }
+
+    /// The angle halfway between the start and end of the range, going up
+    /// from its start.
+    ///
+    /// Returns None for Full, which has no start or end, and for Empty.
+    pub fn midpoint(&self, w: &Wrange<TotalFloat<F>>) -> Option<F> {
+        match w.normalized() {
+            Wrange::Convergent(Bounds(a, _)) | Wrange::Divergent(Bounds(a, _)) => {
+                let start = a.inner().0;
+                Some(self.wrap(start + self.measure(w) / F::TWO).0)
+            }
+            Wrange::Empty | Wrange::Full => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_float() {
+        assert!(TotalFloat(-0.0f64) < TotalFloat(0.0));
+        assert!(TotalFloat(f64::NAN) > TotalFloat(f64::INFINITY));
+        assert_eq!(TotalFloat(f32::NAN), TotalFloat(f32::NAN));
+        assert_eq!("1.5".parse::<TotalFloat<f64>>(), Ok(TotalFloat(1.5)));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let deg = Angle::<f64>::degrees();
+        assert_eq!(deg.wrap(370.0), TotalFloat(10.0));
+        assert_eq!(deg.wrap(-10.0), TotalFloat(350.0));
+        assert_eq!(deg.wrap(-0.0), TotalFloat(0.0));
+        assert_eq!(deg.wrap(360.0), TotalFloat(0.0));
+        assert_eq!(deg.wrap(-1e-20), TotalFloat(0.0));
+    }
+
+    #[test]
+    fn test_sector() {
+        let deg = Angle::<f64>::degrees();
+        let s = deg.sector(350.0, 10.0);
+        assert!(matches!(s, Wrange::Divergent(_)));
+        assert!(deg.contains(&s, 355.0));
+        assert!(deg.contains(&s, 0.0));
+        assert!(deg.contains(&s, -0.0));
+        assert!(deg.contains(&s, 370.0));
+        assert!(!deg.contains(&s, 180.0));
+        assert!(!deg.contains(&s, f64::NAN));
+        assert_eq!(deg.measure(&s), 20.0);
+        assert_eq!(deg.midpoint(&s), Some(0.0));
+
+        let s = deg.sector(90.0, 180.0);
+        assert_eq!(deg.measure(&s), 90.0);
+        assert_eq!(deg.midpoint(&s), Some(135.0));
+
+        assert_eq!(deg.measure(&Wrange::Full), 360.0);
+        assert_eq!(deg.midpoint(&Wrange::Full), None);
+        assert_eq!(deg.midpoint(&Wrange::Empty), None);
+    }
+
+    #[test]
+    fn test_radians() {
+        use core::f32::consts::{FRAC_PI_2, PI};
+        let rad = Angle::<f32>::radians();
+        let s = rad.sector(3.0 * FRAC_PI_2, FRAC_PI_2);
+        assert!(rad.contains(&s, 0.0));
+        assert!(!rad.contains(&s, PI));
+        assert!((rad.measure(&s) - PI).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_text() {
+        let deg = Angle::<f64>::degrees();
+        let s: Wrange<TotalFloat<f64>> = "[350, 10)".parse().unwrap();
+        assert!(deg.contains(&s, 5.5));
+        assert!(!deg.contains(&s, 10.0));
+        assert_eq!(s.to_string(), "[350, 10)");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_period() {
+        Angle::new(f64::INFINITY);
+    }
+}
@@ -0,0 +1,224 @@
+//! Operations which only make sense when the domain of a Wrange is a
+//! fixed-width integer type, so that the MIN and MAX of the domain are known
+//! and arithmetic can wrap around from one to the other.
+
+#[cfg(feature = "alloc")]
+use crate::WrangeSet;
+use crate::{Bound, Bounds, Wrange};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// A fixed-width integer type, treated as a ring of `SIZE` values in which
+/// MAX is immediately followed by MIN.
+pub trait WrappingInteger: PartialOrd + Ord + Hash + Clone + Copy + core::fmt::Debug {
+    /// The number of distinct values of this type
+    const SIZE: u128;
+
+    /// The number of steps needed to get from MIN up to this value
+    fn to_offset(self) -> u128;
+
+    /// The value reached by taking this many steps up from MIN, wrapping past MAX
+    fn from_offset(offset: u128) -> Self;
+
+    /// Addition which wraps past MAX (or MIN)
+    fn wrapping_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_wrapping_integer {
+    ($($t:ty),*) => {
+        $(
+            impl WrappingInteger for $t {
+                const SIZE: u128 = 1 << <$t>::BITS;
+
+                fn to_offset(self) -> u128 {
+                    self.wrapping_sub(<$t>::MIN) as u128 % Self::SIZE
+                }
+
+                fn from_offset(offset: u128) -> Self {
+                    <$t>::MIN.wrapping_add((offset % Self::SIZE) as $t)
+                }
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$t>::wrapping_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapping_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Offset of the first value on the inside of a start bound,
+/// which may lie just beyond MAX
+fn first_offset<T: WrappingInteger>(b: &Bound<T>) -> i128 {
+    match b {
+        Bound::Inclusive(x) => x.to_offset() as i128,
+        Bound::Exclusive(x) => x.to_offset() as i128 + 1,
+    }
+}
+
+/// Offset of the last value on the inside of an end bound,
+/// which may lie just before MIN
+fn last_offset<T: WrappingInteger>(b: &Bound<T>) -> i128 {
+    match b {
+        Bound::Inclusive(x) => x.to_offset() as i128,
+        Bound::Exclusive(x) => x.to_offset() as i128 - 1,
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: WrappingInteger,
+{
+    /// The number of values contained in this range
+    pub fn measure(&self) -> u128 {
+        use Wrange::*;
+        let measure = match self.normalized() {
+            Empty => 0,
+            Full => T::SIZE as i128,
+            Convergent(Bounds(a, b)) => (last_offset(&b) - first_offset(&a) + 1).max(0),
+            Divergent(Bounds(a, b)) => {
+                (T::SIZE as i128 - first_offset(&a)).max(0) + (last_offset(&b) + 1).max(0)
+            }
+        };
+        measure as u128
+    }
+
+    /// The first and last values contained in this range, going around the
+    /// ring from start to end, or None if it contains no values at all.
+    pub(crate) fn first_and_last(&self) -> Option<(T, T)> {
+        use Wrange::*;
+        let size = T::SIZE as i128;
+        let (first, last) = match self.normalized() {
+            Empty => return None,
+            Full => (0, size - 1),
+            Convergent(Bounds(a, b)) => (first_offset(&a), last_offset(&b)),
+            Divergent(Bounds(a, b)) => match (first_offset(&a), last_offset(&b)) {
+                // nothing above the start, so only the part starting from MIN remains
+                (first, last) if first == size => (0, last),
+                // nothing below the end, so only the part ending at MAX remains
+                (first, -1) => (first, size - 1),
+                (first, last) => (first, last + size),
+            },
+        };
+        if last < first {
+            None
+        } else {
+            Some((T::from_offset(first as u128), T::from_offset(last as u128)))
+        }
+    }
+
+    /// Move both endpoints of this range around the ring by `delta`, using
+    /// wrapping addition. The result is Convergent or Divergent depending on
+    /// where the endpoints land, so the number of values contained is unchanged.
+    pub fn shift_by(&self, delta: T) -> Self {
+        use Wrange::*;
+        let shift = |b: &Bound<T>| b.map(|x| x.wrapping_add(delta));
+        match self {
+            Empty => Empty,
+            Full => Full,
+            // colocated endpoints stay colocated, so keep the original shape
+            Convergent(Bounds(a, b)) if a.overlaps(b) => Convergent(Bounds(shift(a), shift(b))),
+            Divergent(Bounds(a, b)) if a.overlaps(b) => Divergent(Bounds(shift(a), shift(b))),
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) => Self::new(shift(a), shift(b)),
+        }
+    }
+
+    /// Shift this range in place. See [`Wrange::shift_by`].
+    pub fn rotate(&mut self, delta: T) {
+        *self = self.shift_by(delta);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WrangeSet<T>
+where
+    T: WrappingInteger,
+{
+    /// Shift every range in this set by `delta`. See [`Wrange::shift_by`].
+    pub fn shift_by(&self, delta: T) -> Self {
+        self.to_vec()
+            .into_iter()
+            .map(|r| r.shift_by(delta))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Shift this set in place. See [`Wrange::shift_by`].
+    pub fn rotate(&mut self, delta: T) {
+        *self = self.shift_by(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(0u8.to_offset(), 0);
+        assert_eq!(255u8.to_offset(), 255);
+        assert_eq!(i8::MIN.to_offset(), 0);
+        assert_eq!((-1i8).to_offset(), 127);
+        assert_eq!(i8::MAX.to_offset(), 255);
+        assert_eq!(i8::from_offset(127), -1);
+        assert_eq!(u64::MAX.to_offset(), u64::MAX as u128);
+        assert_eq!(u64::from_offset(u64::SIZE), 0);
+    }
+
+    #[test]
+    fn test_measure() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(Wrange::<u8>::Empty.measure(), 0);
+        assert_eq!(Wrange::<u8>::Full.measure(), 256);
+        assert_eq!(Wrange::<u64>::Full.measure(), 1 << 64);
+        assert_eq!(Wrange::<u8>::new_inclusive(3, 3).measure(), 1);
+        assert_eq!(Wrange::<u8>::new_exclusive(3, 3).measure(), 0);
+        assert_eq!(Wrange::<u8>::new_exclusive(3, 4).measure(), 0);
+        assert_eq!(Wrange::<u8>::new(Inclusive(3), Exclusive(3)).measure(), 1);
+        assert_eq!(Wrange::<u8>::new(Inclusive(3), Exclusive(7)).measure(), 4);
+        assert_eq!(Wrange::<u8>::new_inclusive(0, 255).measure(), 256);
+        assert_eq!(Wrange::<u8>::new_inclusive(250, 5).measure(), 12);
+        assert_eq!(Wrange::<u8>::new_exclusive(255, 0).measure(), 0);
+        assert_eq!(
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7))).measure(),
+            255
+        );
+        assert_eq!(Wrange::<i8>::new_inclusive(-1, 1).measure(), 3);
+        assert_eq!(Wrange::<i8>::new_inclusive(100, -100).measure(), 57);
+    }
+
+    #[test]
+    fn test_shift_reclassifies() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 255).shift_by(10),
+            Wrange::new_inclusive(4, 9)
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(240, 250).shift_by(10),
+            Divergent(Bounds(Inclusive(250), Inclusive(4)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).shift_by(10),
+            Convergent(Bounds(Inclusive(4), Inclusive(15)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(2, 8).shift_by(250),
+            Divergent(Bounds(Inclusive(252), Inclusive(2)))
+        );
+        assert_eq!(
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7))).shift_by(250),
+            Divergent(Bounds(Exclusive(1), Exclusive(1)))
+        );
+        assert_eq!(
+            Wrange::<i8>::new_inclusive(100, 120).shift_by(20),
+            Divergent(Bounds(Inclusive(120), Inclusive(-116)))
+        );
+    }
+}
@@ -3,6 +3,19 @@
 //! When the start bound is less than the end bound, it is a normal continuous Range.
 //! When the start bound is greater than the end bound, it is treated as the union
 //! of the ranges [MIN, end] + [start, MAX].
+//!
+//! With default features disabled the crate is `no_std`, and `Wrange` and
+//! `Bound` need no allocator at all. `WrangeSet` and everything built on it
+//! need the `alloc` feature, and the I/O methods and `std::error::Error` impls
+//! need the `std` feature, which is on by default. The `svg` feature adds
+//! the `svg` module, for drawing sets as circular diagrams.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod macros;
 
 mod bound;
 pub use bound::{Bound, Bounds};
@@ -10,7 +23,55 @@ pub use bound::{Bound, Bounds};
 mod wrange;
 pub use crate::wrange::Wrange;
 
+#[cfg(feature = "alloc")]
 mod wrange_set;
+#[cfg(feature = "alloc")]
 pub use wrange_set::WrangeSet;
 
+mod segment;
+
+mod relation;
+pub use relation::Relation;
+
+mod integer;
+pub use integer::WrappingInteger;
+
+mod resize;
+
+mod position;
+
+#[cfg(feature = "alloc")]
+mod split;
+
+mod quantized;
+pub use quantized::QuantizedWrange;
+
+#[cfg(feature = "alloc")]
+pub mod encoding;
+#[cfg(feature = "alloc")]
+pub use encoding::DecodeError;
+
+#[cfg(feature = "alloc")]
 pub mod ascii;
+
+#[cfg(feature = "alloc")]
+mod text;
+#[cfg(feature = "alloc")]
+pub use text::{ParseError, ParseErrorKind};
+
+#[cfg(feature = "alloc")]
+pub mod fingerprint;
+
+#[cfg(feature = "alloc")]
+pub mod ring;
+
+#[cfg(feature = "svg")]
+pub mod svg;
+
+pub mod serial;
+pub use serial::SerialWrange;
+
+mod float;
+pub use float::{Angle, Float, TotalFloat};
+
+pub mod schedule;
@@ -0,0 +1,152 @@
+//! Macros for writing ranges and sets of ranges in interval notation.
+
+/// Build a [`Wrange`](crate::Wrange) from interval notation:
+///
+/// - `wrange![a..b]` is [a, b)
+/// - `wrange![a..=b]` or `wrange![[a, b]]` is [a, b]
+/// - `wrange![open a..b]` or `wrange![(a, b)]` is (a, b)
+/// - `wrange![open a..=b]` is (a, b]
+/// - `wrange![full]` or `wrange![..]` is Full, and `wrange![empty]` is Empty
+///
+/// Every form but the last goes through [`Wrange::new`](crate::Wrange::new),
+/// so the range is Divergent if `b` comes before `a`, as in `wrange![250..=5]`.
+///
+/// Rust only accepts balanced brackets, so `wrange![(a, b]]` and
+/// `wrange![[a, b)]` are impossible, and are written `wrange![open a..=b]`
+/// and `wrange![a..b]` instead, with `open` standing in for the opening
+/// parenthesis. The endpoints after `open` must be single tokens, such as
+/// literals or names, so any other expression has to be put in braces, as in
+/// `wrange![open {-5}..5]`. (Parentheses would do too, but rustfmt reads
+/// `open (-5)` as a call.) Longer endpoints are fine without `open`,
+/// as in `wrange![lo + 1..hi]`, but the range then goes through `core::ops`,
+/// where clippy flags those that wrap as empty.
+#[macro_export]
+macro_rules! wrange {
+    (full) => {
+        $crate::Wrange::Full
+    };
+    (empty) => {
+        $crate::Wrange::Empty
+    };
+    ($a:tt ..= $b:tt) => {
+        $crate::Wrange::new($crate::Bound::Inclusive($a), $crate::Bound::Inclusive($b))
+    };
+    ($a:tt .. $b:tt) => {
+        $crate::Wrange::new($crate::Bound::Inclusive($a), $crate::Bound::Exclusive($b))
+    };
+    (open $a:tt ..= $b:tt) => {{
+        // endpoints may come in braces
+        #[allow(unused_braces)]
+        let (a, b) = ($a, $b);
+        $crate::Wrange::new($crate::Bound::Exclusive(a), $crate::Bound::Inclusive(b))
+    }};
+    (open $a:tt .. $b:tt) => {{
+        #[allow(unused_braces)]
+        let (a, b) = ($a, $b);
+        $crate::Wrange::new($crate::Bound::Exclusive(a), $crate::Bound::Exclusive(b))
+    }};
+    ([$a:expr, $b:expr]) => {
+        $crate::Wrange::new($crate::Bound::Inclusive($a), $crate::Bound::Inclusive($b))
+    };
+    (($a:expr, $b:expr)) => {
+        $crate::Wrange::new($crate::Bound::Exclusive($a), $crate::Bound::Exclusive($b))
+    };
+    ($r:expr) => {
+        $crate::Wrange::from($r)
+    };
+}
+
+/// Build a [`WrangeSet`](crate::WrangeSet) from a comma-separated list of
+/// ranges, each written as for [`wrange!`], e.g.
+/// `wrange_set![2..7, (10, 20), 250..=5]`.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! wrange_set {
+    ($($t:tt)*) => {
+        $crate::__wrange_set!(@ [] [] $($t)*)
+    };
+}
+
+/// Split the input of [`wrange_set!`] at its top-level commas, collecting
+/// each range as it is completed
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __wrange_set {
+    (@ [$($done:expr,)*] []) => {
+        <$crate::WrangeSet<_> as ::core::iter::FromIterator<_>>::from_iter([$($done,)*])
+    };
+    (@ [$($done:expr,)*] [$($range:tt)+]) => {
+        $crate::__wrange_set!(@ [$($done,)*] [$($range)+] ,)
+    };
+    (@ [$($done:expr,)*] [$($range:tt)+] , $($rest:tt)*) => {
+        $crate::__wrange_set!(@ [$($done,)* $crate::wrange!($($range)+),] [] $($rest)*)
+    };
+    (@ $done:tt [$($range:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__wrange_set!(@ $done [$($range)* $next] $($rest)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bound::*;
+    use crate::{Bounds, Wrange};
+
+    #[test]
+    fn test_wrange() {
+        assert_eq!(
+            wrange![2..7],
+            Wrange::Convergent(Bounds(Inclusive(2), Exclusive(7)))
+        );
+        assert_eq!(wrange![2..=7], Wrange::new_inclusive(2, 7));
+        assert_eq!(wrange![[2, 7]], Wrange::new_inclusive(2, 7));
+        assert_eq!(wrange![(2, 7)], Wrange::new_exclusive(2, 7));
+        assert_eq!(wrange![open 2..7], Wrange::new_exclusive(2, 7));
+        assert_eq!(
+            wrange![open 2..=7],
+            Wrange::Convergent(Bounds(Exclusive(2), Inclusive(7)))
+        );
+        assert_eq!(
+            wrange![open 250u8..=5],
+            Wrange::Divergent(Bounds(Exclusive(250), Inclusive(5)))
+        );
+        assert_eq!(
+            wrange![250u8..=5],
+            Wrange::Divergent(Bounds(Inclusive(250), Inclusive(5)))
+        );
+        let neg = -5;
+        assert_eq!(wrange![open neg..5], Wrange::new_exclusive(-5, 5));
+        assert_eq!(wrange![open {-5}..5], Wrange::new_exclusive(-5, 5));
+        // a name `open` is still an ordinary endpoint
+        let open = 3;
+        assert_eq!(wrange![open..5], Wrange::new(Inclusive(3), Exclusive(5)));
+        assert_eq!(wrange![full], Wrange::<u8>::Full);
+        assert_eq!(wrange![..], Wrange::<u8>::Full);
+        assert_eq!(wrange![empty], Wrange::<u8>::Empty);
+
+        let (lo, hi) = (10u32, 20);
+        assert_eq!(
+            wrange![lo + 1..hi],
+            Wrange::new(Inclusive(11), Exclusive(20))
+        );
+        assert_eq!(wrange![[hi, lo * 2]], Wrange::new_inclusive(20, 20));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_wrange_set() {
+        use crate::WrangeSet;
+
+        assert_eq!(
+            wrange_set![2..7, (10, 20), 250u8..=5, open 30..=40,],
+            WrangeSet::from(vec![
+                wrange![2..7],
+                Wrange::new_exclusive(10, 20),
+                Wrange::new_inclusive(250, 5),
+                Wrange::new(Exclusive(30), Inclusive(40)),
+            ])
+        );
+        assert_eq!(wrange_set![full], WrangeSet::<u8>::from(Wrange::Full));
+        assert_eq!(wrange_set![], WrangeSet::<u8>::default());
+    }
+}
@@ -0,0 +1,165 @@
+//! Locating points relative to ranges over integer domains.
+
+#[cfg(feature = "alloc")]
+use crate::WrangeSet;
+use crate::{Wrange, WrappingInteger};
+
+/// Number of steps going up the ring from `from` to `to`
+fn steps_up<T: WrappingInteger>(from: T, to: T) -> u128 {
+    (to.to_offset() + T::SIZE - from.to_offset()) % T::SIZE
+}
+
+impl<T> Wrange<T>
+where
+    T: WrappingInteger,
+{
+    /// The value halfway between the first and last values contained in this
+    /// range, going around the ring from start to end. When the range contains
+    /// an even number of values, this is the lower of the two middle values.
+    ///
+    /// Returns None for Full, which has no start or end, and for any range
+    /// which contains no values.
+    pub fn midpoint(&self) -> Option<T> {
+        if let Wrange::Full = self {
+            return None;
+        }
+        let (first, last) = self.first_and_last()?;
+        Some(T::from_offset(
+            first.to_offset() + steps_up(first, last) / 2,
+        ))
+    }
+
+    /// The number of steps around the ring from `x` to the nearest value
+    /// contained in this range, in either direction. This is 0 if `x` is
+    /// contained in the range, and None if the range contains no values.
+    pub fn distance_to(&self, x: &T) -> Option<u128> {
+        self.nearest_to(x).map(|(distance, _)| distance)
+    }
+
+    /// The value in this range nearest to `x`, along with its distance from `x`.
+    /// Ties are broken in favor of the value above `x`.
+    fn nearest_to(&self, x: &T) -> Option<(u128, T)> {
+        let (first, last) = self.first_and_last()?;
+        if self.contains(x) {
+            Some((0, *x))
+        } else {
+            let above = steps_up(*x, first);
+            let below = steps_up(last, *x);
+            if above <= below {
+                Some((above, first))
+            } else {
+                Some((below, last))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WrangeSet<T>
+where
+    T: WrappingInteger,
+{
+    /// The value contained in this set which is nearest to `x`, going around
+    /// the ring in either direction, or None if the set contains no values.
+    /// This is `x` itself if it is contained in the set. Ties are broken in
+    /// favor of the value above `x`.
+    pub fn nearest_member(&self, x: &T) -> Option<T> {
+        self.to_vec()
+            .iter()
+            .filter_map(|r| r.nearest_to(x))
+            // among equidistant candidates, the one above x is reached first
+            // going up the ring, so prefer the smaller step count from x
+            .min_by_key(|(distance, t)| (*distance, steps_up(*x, *t)))
+            .map(|(_, t)| t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bound, Bounds};
+
+    #[test]
+    fn test_start_end() {
+        use Bound::*;
+
+        let r = Wrange::<u8>::new(Inclusive(250), Exclusive(5));
+        assert_eq!(r.start(), Some(&Inclusive(250)));
+        assert_eq!(r.end(), Some(&Exclusive(5)));
+        assert_eq!(Wrange::<u8>::Empty.start(), None);
+        assert_eq!(Wrange::<u8>::Full.end(), None);
+    }
+
+    #[test]
+    fn test_midpoint() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(Wrange::<u8>::new_inclusive(10, 20).midpoint(), Some(15));
+        assert_eq!(Wrange::<u8>::new_inclusive(10, 21).midpoint(), Some(15));
+        assert_eq!(Wrange::<u8>::new_exclusive(10, 20).midpoint(), Some(15));
+        assert_eq!(Wrange::<u8>::new_inclusive(7, 7).midpoint(), Some(7));
+        assert_eq!(Wrange::<u8>::new_inclusive(250, 10).midpoint(), Some(2));
+        assert_eq!(Wrange::<u8>::new_inclusive(200, 10).midpoint(), Some(233));
+        assert_eq!(
+            Divergent(Bounds(Exclusive(5u8), Exclusive(5))).midpoint(),
+            Some(133)
+        );
+        assert_eq!(
+            Divergent(Bounds(Exclusive(255u8), Inclusive(3))).midpoint(),
+            Some(1)
+        );
+        assert_eq!(
+            Wrange::<i8>::new_inclusive(120, -120).midpoint(),
+            Some(-128)
+        );
+        assert_eq!(Wrange::<u8>::new_exclusive(10, 11).midpoint(), None);
+        assert_eq!(Wrange::<u8>::Empty.midpoint(), None);
+        assert_eq!(Wrange::<u8>::Full.midpoint(), None);
+    }
+
+    #[test]
+    fn test_distance_to() {
+        use Bound::*;
+
+        let r = Wrange::<u8>::new_inclusive(10, 20);
+        assert_eq!(r.distance_to(&15), Some(0));
+        assert_eq!(r.distance_to(&10), Some(0));
+        assert_eq!(r.distance_to(&25), Some(5));
+        assert_eq!(r.distance_to(&5), Some(5));
+        assert_eq!(r.distance_to(&250), Some(16));
+        assert_eq!(r.distance_to(&140), Some(120));
+
+        let r = Wrange::<u8>::new(Exclusive(250), Exclusive(10));
+        assert_eq!(r.distance_to(&0), Some(0));
+        assert_eq!(r.distance_to(&250), Some(1));
+        assert_eq!(r.distance_to(&10), Some(1));
+        assert_eq!(r.distance_to(&100), Some(91));
+
+        assert_eq!(Wrange::<u8>::Full.distance_to(&100), Some(0));
+        assert_eq!(Wrange::<u8>::Empty.distance_to(&100), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_nearest_member() {
+        let set: WrangeSet<u8> = vec![
+            Wrange::new_inclusive(10, 20),
+            Wrange::new_inclusive(30, 40),
+            Wrange::new_inclusive(250, 2),
+        ]
+        .into();
+
+        assert_eq!(set.nearest_member(&15), Some(15));
+        assert_eq!(set.nearest_member(&22), Some(20));
+        assert_eq!(set.nearest_member(&27), Some(30));
+        assert_eq!(set.nearest_member(&25), Some(30), "ties go upwards");
+        assert_eq!(set.nearest_member(&6), Some(10), "ties go upwards");
+        assert_eq!(set.nearest_member(&5), Some(2));
+        assert_eq!(set.nearest_member(&100), Some(40));
+        assert_eq!(set.nearest_member(&200), Some(250));
+
+        let empty: WrangeSet<u8> = vec![Wrange::Empty, Wrange::new_exclusive(3, 4)].into();
+        assert_eq!(empty.nearest_member(&100), None);
+    }
+}
@@ -0,0 +1,207 @@
+//! Ranges over u32 whose endpoints are snapped to a grid of equally sized
+//! chunks, so that peers exchanging ranges agree exactly on their boundaries.
+
+use crate::Wrange;
+
+/// A range over u32 made up of whole chunks, where each chunk contains
+/// 2^`power` values and chunk `i` begins at `i * 2^power`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuantizedWrange {
+    start: u32,
+    count: u64,
+    power: u8,
+}
+
+impl QuantizedWrange {
+    /// The range of `count` chunks of size 2^`power`, beginning with chunk
+    /// number `start` and wrapping around past the last chunk if needed.
+    ///
+    /// Returns None if `power` is greater than 32, or if `start` or `count`
+    /// exceed the number of chunks in the domain.
+    pub fn new(start: u32, count: u64, power: u8) -> Option<Self> {
+        if power > 32 {
+            return None;
+        }
+        let chunks = 1u64 << (32 - power);
+        if start as u64 >= chunks || count > chunks {
+            None
+        } else {
+            Some(Self {
+                start,
+                count,
+                power,
+            })
+        }
+    }
+
+    /// The index of the first chunk
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// The number of chunks in the range
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The chunk size exponent
+    pub fn power(&self) -> u8 {
+        self.power
+    }
+
+    /// The number of values in each chunk
+    pub fn chunk_size(&self) -> u64 {
+        1 << self.power
+    }
+
+    /// The number of chunks needed to cover the whole domain
+    pub fn chunks_in_domain(&self) -> u64 {
+        1 << (32 - self.power)
+    }
+}
+
+impl From<QuantizedWrange> for Wrange<u32> {
+    fn from(q: QuantizedWrange) -> Self {
+        Wrange::with_length_from(
+            (q.start as u64 * q.chunk_size()) as u32,
+            q.count as u128 * q.chunk_size() as u128,
+        )
+    }
+}
+
+impl Wrange<u32> {
+    /// The smallest range made of whole chunks of size 2^`power` which
+    /// contains every value in this range.
+    ///
+    /// Panics if `power` is greater than 32.
+    pub fn quantize_outward(&self, power: u8) -> QuantizedWrange {
+        self.quantize(power, |first, last, size| (first / size, last / size + 1))
+    }
+
+    /// The largest range made of whole chunks of size 2^`power` which
+    /// contains only values in this range.
+    ///
+    /// Panics if `power` is greater than 32.
+    pub fn quantize_inward(&self, power: u8) -> QuantizedWrange {
+        self.quantize(power, |first, last, size| {
+            ((first + size - 1) / size, (last + 1) / size)
+        })
+    }
+
+    /// Quantize by way of a function which maps the offsets of the first and
+    /// last values of this range onto a half-open range of chunk indices.
+    /// The offsets are unwrapped, so the last may lie beyond the end of the domain.
+    fn quantize<F>(&self, power: u8, chunks_between: F) -> QuantizedWrange
+    where
+        F: Fn(u64, u64, u64) -> (u64, u64),
+    {
+        assert!(power <= 32, "chunk size exponent must be at most 32");
+        let size = 1u64 << power;
+        let chunks = 1u64 << (32 - power);
+        let (lo, hi) = match (self, self.first_and_last()) {
+            (Wrange::Full, _) => (0, chunks),
+            (_, Some((first, _))) => {
+                let first = first as u64;
+                chunks_between(first, first + self.measure() as u64 - 1, size)
+            }
+            (_, None) => (0, 0),
+        };
+        let count = hi.saturating_sub(lo).min(chunks);
+        // Empty and Full have no start, so always use the first chunk
+        let start = if count == 0 || count == chunks {
+            0
+        } else {
+            (lo % chunks) as u32
+        };
+        QuantizedWrange {
+            start,
+            count,
+            power,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bound, Bounds};
+
+    #[test]
+    fn test_new() {
+        assert!(QuantizedWrange::new(0, 1 << 32, 0).is_some());
+        assert!(QuantizedWrange::new(0, (1 << 32) + 1, 0).is_none());
+        assert!(QuantizedWrange::new(15, 16, 28).is_some());
+        assert!(QuantizedWrange::new(16, 1, 28).is_none());
+        assert!(QuantizedWrange::new(0, 1, 32).is_some());
+        assert!(QuantizedWrange::new(0, 1, 33).is_none());
+    }
+
+    #[test]
+    fn test_into_wrange() {
+        use Bound::*;
+        use Wrange::*;
+
+        let q = |start, count, power| -> Wrange<u32> {
+            QuantizedWrange::new(start, count, power).unwrap().into()
+        };
+
+        assert_eq!(q(0, 0, 4), Empty);
+        assert_eq!(q(3, 0, 4), Empty);
+        assert_eq!(q(1, 2, 4), Wrange::new_inclusive(16, 47));
+        assert_eq!(q(0, 1 << 28, 4), Full);
+        assert_eq!(q(0, 1, 32), Full);
+        assert_eq!(q(7, 1 << 32, 0), Full);
+        assert_eq!(
+            q(15, 2, 28),
+            Divergent(Bounds(Inclusive(0xf000_0000), Inclusive(0x0fff_ffff)))
+        );
+    }
+
+    #[test]
+    fn test_quantize() {
+        use Bound::*;
+        use Wrange::*;
+
+        let outward = |w: Wrange<u32>, power| Wrange::from(w.quantize_outward(power));
+        let inward = |w: Wrange<u32>, power| Wrange::from(w.quantize_inward(power));
+
+        let w = Wrange::new_inclusive(20, 60);
+        assert_eq!(outward(w, 4), Wrange::new_inclusive(16, 63));
+        assert_eq!(inward(w, 4), Wrange::new_inclusive(32, 47));
+        assert_eq!(outward(w, 0), w);
+        assert_eq!(inward(w, 0), w);
+        assert_eq!(inward(w, 5), Empty);
+        assert_eq!(outward(w, 32), Full);
+
+        let w = Wrange::new_inclusive(16, 47);
+        assert_eq!(outward(w, 4), w);
+        assert_eq!(inward(w, 4), w);
+
+        let w = Wrange::new(Exclusive(15), Exclusive(48));
+        assert_eq!(outward(w, 4), Wrange::new_inclusive(16, 47));
+        assert_eq!(inward(w, 4), Wrange::new_inclusive(16, 47));
+
+        let w = Wrange::new_inclusive(u32::MAX - 20, 20);
+        assert_eq!(
+            outward(w, 4),
+            Divergent(Bounds(Inclusive(u32::MAX - 31), Inclusive(31)))
+        );
+        assert_eq!(
+            inward(w, 4),
+            Divergent(Bounds(Inclusive(u32::MAX - 15), Inclusive(15)))
+        );
+
+        // covers all but one value, with both ends in the same chunk
+        let w = Divergent(Bounds(Exclusive(20), Exclusive(20)));
+        assert_eq!(outward(w, 4), Full);
+        assert_eq!(
+            inward(w, 4),
+            Divergent(Bounds(Inclusive(32), Inclusive(15)))
+        );
+
+        assert_eq!(outward(Full, 4), Full);
+        assert_eq!(inward(Full, 4), Full);
+        assert_eq!(outward(Empty, 4), Empty);
+        assert_eq!(inward(Empty, 4), Empty);
+    }
+}
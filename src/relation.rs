@@ -0,0 +1,218 @@
+//! Cheap predicates describing how two Wranges relate to each other,
+//! computed directly from their bounds without building a WrangeSet.
+
+use crate::{Bound, Wrange};
+use core::hash::Hash;
+
+/// How one Wrange is positioned relative to another, in the spirit of
+/// Allen's interval algebra.
+///
+/// On a ring there is no "before" or "after": two ranges which share no points
+/// are simply Disjoint, unless they sit directly next to each other. A ring also
+/// allows two relations which cannot occur on a line, where both ends of one
+/// range meet or overlap both ends of the other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// No points in common, and not adjacent.
+    /// Also used whenever either range is empty.
+    Disjoint,
+    /// No points in common, and the end of this range abuts the start of the other.
+    Meets,
+    /// No points in common, and the end of the other range abuts the start of this one.
+    MetBy,
+    /// No points in common, and each range abuts the other at both ends,
+    /// so that together they cover the whole ring.
+    MeetsAndMetBy,
+    /// The end of this range lies within the other, and the start of the other
+    /// lies within this one.
+    Overlaps,
+    /// The end of the other range lies within this one, and the start of this
+    /// one lies within the other.
+    OverlappedBy,
+    /// Both ends of each range lie within the other, so that their intersection
+    /// is split into two pieces.
+    OverlapsAndOverlappedBy,
+    /// This range is strictly contained in the other and shares its start.
+    Starts,
+    /// The other range is strictly contained in this one and shares its start.
+    StartedBy,
+    /// This range is strictly contained in the other and shares its end.
+    Finishes,
+    /// The other range is strictly contained in this one and shares its end.
+    FinishedBy,
+    /// This range is strictly contained in the other, sharing neither end.
+    During,
+    /// The other range is strictly contained in this one, sharing neither end.
+    Contains,
+    /// Both ranges cover exactly the same points.
+    Equal,
+}
+
+/// Whether an end bound and a start bound sit at the same position with
+/// complementary inclusivity, so that the point belongs to exactly one side.
+fn abuts<T: PartialEq>(end: &Bound<T>, start: &Bound<T>) -> bool {
+    use Bound::*;
+    match (end, start) {
+        (Inclusive(x), Exclusive(y)) | (Exclusive(x), Inclusive(y)) => x == y,
+        _ => false,
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: Ord + Clone,
+{
+    /// Whether the given point lies within this range.
+    pub fn contains(&self, x: &T) -> bool {
+        self.segments().any(|s| s.covers_point(x))
+    }
+
+    /// Whether the two ranges have at least one point in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.segments()
+            .any(|s| other.segments().any(|o| !s.intersection(&o).is_empty()))
+    }
+
+    /// Whether the two ranges have no points in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        !self.overlaps(other)
+    }
+
+    /// Whether every point in this range is also in the other.
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.segments()
+            .all(|s| other.segments().any(|o| s.is_subset_of(&o)))
+    }
+
+    /// Whether every point in the other range is also in this one.
+    pub fn is_superset_of(&self, other: &Self) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// Whether the two ranges are disjoint but directly adjacent, i.e. an end of
+    /// one range and the start of the other sit at the same position, with
+    /// exactly one of them being inclusive.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.is_disjoint(other) && (self.meets(other) || other.meets(self))
+    }
+
+    /// Describe how this range is positioned relative to another.
+    pub fn relation(&self, other: &Self) -> Relation {
+        use Relation::*;
+        let subset = self.is_subset_of(other);
+        let superset = self.is_superset_of(other);
+        if subset && superset {
+            return Equal;
+        }
+        if self.is_disjoint(other) {
+            return match (self.meets(other), other.meets(self)) {
+                (true, true) => MeetsAndMetBy,
+                (true, false) => Meets,
+                (false, true) => MetBy,
+                (false, false) => Disjoint,
+            };
+        }
+        if subset || superset {
+            let (inner, outer) = if subset { (self, other) } else { (other, self) };
+            let (same_start, same_end) = match (inner.edges(), outer.edges()) {
+                (Some((i0, i1)), Some((o0, o1))) => (i0 == o0, i1 == o1),
+                _ => (false, false),
+            };
+            return match (subset, same_start, same_end) {
+                (true, true, _) => Starts,
+                (true, false, true) => Finishes,
+                (true, false, false) => During,
+                (false, true, _) => StartedBy,
+                (false, false, true) => FinishedBy,
+                (false, false, false) => Contains,
+            };
+        }
+        // Neither range is Empty or Full at this point, so both have edges
+        match (self.edges(), other.edges()) {
+            (Some((s0, s1)), Some(_)) => {
+                let start_within = other.segments().any(|o| o.covers_start(s0));
+                let end_within = other.segments().any(|o| o.covers_end(s1));
+                match (start_within, end_within) {
+                    (true, true) => OverlapsAndOverlappedBy,
+                    (false, true) => Overlaps,
+                    _ => OverlappedBy,
+                }
+            }
+            _ => unreachable!("partially overlapping ranges must both have edges"),
+        }
+    }
+
+    /// Whether the end of this range abuts the start of the other
+    fn meets(&self, other: &Self) -> bool {
+        match (self.edges(), other.edges()) {
+            (Some((_, end)), Some((start, _))) => abuts(end, start),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::ascii::ascii;
+
+    fn one(s: &str) -> Wrange<u8> {
+        ascii(s).to_vec()[0]
+    }
+
+    #[test]
+    fn test_relation_convergent() {
+        use Relation::*;
+
+        let r = |a, b| one(a).relation(&one(b));
+
+        assert_eq!(r("  o---o       ", "  o---o       "), Equal);
+        assert_eq!(r("  o---o       ", "         o--o "), Disjoint);
+        assert_eq!(r("  o---x       ", "      o-----o "), Meets);
+        assert_eq!(r("      x-----o ", "  o---o       "), MetBy);
+        assert_eq!(r("  o---x       ", "      x-----o "), Disjoint);
+        assert_eq!(r("  o-----o     ", "      o-----o "), Overlaps);
+        assert_eq!(r("      o-----o ", "  o-----o     "), OverlappedBy);
+        assert_eq!(r("  o--o        ", "  o-----o     "), Starts);
+        assert_eq!(r("  o-----o     ", "  o--o        "), StartedBy);
+        assert_eq!(r("     o--o     ", "  o-----o     "), Finishes);
+        assert_eq!(r("  o-----o     ", "     o--o     "), FinishedBy);
+        assert_eq!(r("    o-o       ", "  o-----o     "), During);
+        assert_eq!(r("  o-----o     ", "    o-o       "), Contains);
+        assert_eq!(r("  x-----o     ", "  o-----o     "), Finishes);
+    }
+
+    #[test]
+    fn test_relation_divergent() {
+        use Relation::*;
+
+        let r = |a, b| one(a).relation(&one(b));
+
+        assert_eq!(r("--o       o---", "  x-------x   "), MeetsAndMetBy);
+        assert_eq!(r("--o       o---", "   o-----o    "), Disjoint);
+        assert_eq!(
+            r("--o       o---", " o--------o   "),
+            OverlapsAndOverlappedBy
+        );
+        assert_eq!(r("--o       o---", "-o        o---"), StartedBy);
+        assert_eq!(r("--o       o---", "--o         o-"), FinishedBy);
+        assert_eq!(r("--o       o---", "-o          o-"), Contains);
+        assert_eq!(r("--o       o---", "-----o      o-"), Overlaps);
+        assert_eq!(r("--o       o---", "--o     o-----"), Finishes);
+        assert_eq!(r("-o          o-", "--o       o---"), During);
+
+        assert_eq!(Wrange::Full.relation(&one("  o--o  ")), Contains);
+        assert_eq!(one("  o--o  ").relation(&Wrange::Full), During);
+        assert_eq!(Wrange::<u8>::Full.relation(&Wrange::Full), Equal);
+        assert_eq!(Wrange::Empty.relation(&one("  o--o  ")), Disjoint);
+    }
+
+    #[test]
+    fn test_touches() {
+        assert!(one("  o--x    ").touches(&one("     o--o ")));
+        assert!(one("     o--o ").touches(&one("  o--x    ")));
+        assert!(!one("  o--o    ").touches(&one("     o--o ")));
+        assert!(!one("  o--x    ").touches(&one("     x--o ")));
+        assert!(!one("  o--x    ").touches(&one("       o-o")));
+    }
+}
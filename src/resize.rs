@@ -0,0 +1,232 @@
+//! Growing and shrinking ranges over integer domains, and constructing
+//! ranges of a given length.
+
+use crate::{Bounds, Wrange, WrappingInteger};
+
+/// Step up the ring from `x` by `n` values
+fn up<T: WrappingInteger>(x: T, n: u128) -> T {
+    T::from_offset(x.to_offset() + n % T::SIZE)
+}
+
+/// Step down the ring from `x` by `n` values
+fn down<T: WrappingInteger>(x: T, n: u128) -> T {
+    T::from_offset(x.to_offset() + T::SIZE - n % T::SIZE)
+}
+
+impl<T> Wrange<T>
+where
+    T: WrappingInteger,
+{
+    /// The range of `len` values beginning at `start`, inclusive.
+    /// Saturates to Full if `len` is at least the size of the domain,
+    /// and gives Empty if `len` is zero.
+    pub fn with_length_from(start: T, len: u128) -> Self {
+        if len == 0 {
+            Self::Empty
+        } else if len >= T::SIZE {
+            Self::Full
+        } else {
+            Self::new_inclusive(start, up(start, len - 1))
+        }
+    }
+
+    /// The range of `len` values centered on `center`.
+    /// When `len` is even, the extra value goes after the center.
+    /// Saturates to Full if `len` is at least the size of the domain,
+    /// and gives Empty if `len` is zero.
+    pub fn with_length_around(center: T, len: u128) -> Self {
+        Self::with_length_from(down(center, len.saturating_sub(1) / 2), len)
+    }
+
+    /// Grow this range by `n` values at each end.
+    /// Saturates to Full once the range would cover the whole domain.
+    /// Empty and Full ranges are unchanged.
+    pub fn expand(&self, n: u128) -> Self {
+        use Wrange::*;
+        match self.normalized() {
+            r if n == 0 => r,
+            Empty => Empty,
+            Full => Full,
+            r if r.measure().saturating_add(n.saturating_mul(2)) >= T::SIZE => Full,
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) => {
+                let (a, b) = (a.map(|x| down(x, n)), b.map(|x| up(x, n)));
+                if a.overlaps(&b) {
+                    // the ends have met around the ring, leaving out just the
+                    // one value between them, which Self::new would read as Empty
+                    Divergent(Bounds(a, b))
+                } else {
+                    Self::new(a, b)
+                }
+            }
+        }
+    }
+
+    /// Shrink this range by `n` values at each end.
+    /// Gives Empty once there would be nothing left of the range.
+    /// Empty and Full ranges are unchanged, since Full has no ends to move.
+    pub fn shrink(&self, n: u128) -> Self {
+        use Wrange::*;
+        match self.normalized() {
+            r if n == 0 => r,
+            Empty => Empty,
+            Full => Full,
+            r if r.measure() <= n.saturating_mul(2) => Empty,
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) => {
+                Self::new(a.map(|x| up(x, n)), b.map(|x| down(x, n)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bound;
+
+    #[test]
+    fn test_with_length() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(Wrange::<u8>::with_length_from(10, 0), Empty);
+        assert_eq!(
+            Wrange::<u8>::with_length_from(10, 1),
+            Wrange::new_inclusive(10, 10)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_from(10, 5),
+            Wrange::new_inclusive(10, 14)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_from(250, 10),
+            Divergent(Bounds(Inclusive(250), Inclusive(3)))
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_from(0, 256),
+            Full,
+            "a length equal to the size of the domain is Full, not [0, 255]"
+        );
+        assert_eq!(Wrange::<u8>::with_length_from(10, 1000), Full);
+        assert_eq!(
+            Wrange::<u8>::with_length_from(1, 255),
+            Wrange::new_inclusive(1, 255)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_from(2, 255),
+            Divergent(Bounds(Inclusive(2), Inclusive(0)))
+        );
+
+        assert_eq!(Wrange::<u8>::with_length_around(10, 0), Empty);
+        assert_eq!(
+            Wrange::<u8>::with_length_around(10, 1),
+            Wrange::new_inclusive(10, 10)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_around(10, 5),
+            Wrange::new_inclusive(8, 12)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_around(10, 4),
+            Wrange::new_inclusive(9, 12)
+        );
+        assert_eq!(
+            Wrange::<u8>::with_length_around(0, 5),
+            Divergent(Bounds(Inclusive(254), Inclusive(2)))
+        );
+        assert_eq!(
+            Wrange::<i8>::with_length_around(127, 3),
+            Divergent(Bounds(Inclusive(126), Inclusive(-128)))
+        );
+        assert_eq!(Wrange::<u64>::with_length_around(0, 1 << 64), Full);
+    }
+
+    #[test]
+    fn test_expand() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).expand(5),
+            Wrange::new_inclusive(5, 25)
+        );
+        assert_eq!(
+            Wrange::<u8>::new(Exclusive(10), Inclusive(20)).expand(5),
+            Wrange::new(Exclusive(5), Inclusive(25))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).expand(15),
+            Divergent(Bounds(Inclusive(251), Inclusive(35)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).expand(10),
+            Divergent(Bounds(Inclusive(240), Inclusive(15)))
+        );
+        assert_eq!(Wrange::<u8>::new_inclusive(250, 5).expand(122), Full);
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).expand(121),
+            Divergent(Bounds(Inclusive(129), Inclusive(126)))
+        );
+        assert_eq!(
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7))).expand(1),
+            Full
+        );
+        assert_eq!(
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7))).expand(0),
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_exclusive(10, 20).expand(123),
+            Divergent(Bounds(Exclusive(143), Exclusive(143)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_exclusive(10, 20).expand(123).measure(),
+            255
+        );
+        assert_eq!(
+            Wrange::<u8>::new_exclusive(250, 6).expand(122),
+            Divergent(Bounds(Exclusive(128), Exclusive(128)))
+        );
+        assert_eq!(Wrange::<u8>::Empty.expand(3), Empty);
+        assert_eq!(Wrange::<u8>::Full.expand(3), Full);
+        assert_eq!(Wrange::<u8>::new_inclusive(3, 3).expand(u128::MAX), Full);
+    }
+
+    #[test]
+    fn test_shrink() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).shrink(5),
+            Wrange::new_inclusive(15, 15)
+        );
+        assert_eq!(Wrange::<u8>::new_inclusive(10, 20).shrink(6), Empty);
+        assert_eq!(
+            Wrange::<u8>::new(Inclusive(10), Exclusive(20)).shrink(2),
+            Wrange::new(Inclusive(12), Exclusive(18))
+        );
+        assert_eq!(
+            Wrange::<u8>::new(Inclusive(10), Exclusive(20)).shrink(5),
+            Empty
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).shrink(3),
+            Divergent(Bounds(Inclusive(253), Inclusive(2)))
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 5).shrink(5),
+            Divergent(Bounds(Inclusive(255), Inclusive(0)))
+        );
+        assert_eq!(Wrange::<u8>::new_inclusive(250, 5).shrink(6), Empty);
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(200, 5).shrink(10),
+            Wrange::new_inclusive(210, 251)
+        );
+        assert_eq!(
+            Divergent(Bounds(Exclusive(5u8), Exclusive(5))).shrink(127),
+            Wrange::new_exclusive(132, 134)
+        );
+        assert_eq!(Wrange::<u8>::Empty.shrink(3), Empty);
+        assert_eq!(Wrange::<u8>::Full.shrink(3), Full);
+    }
+}
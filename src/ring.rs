@@ -0,0 +1,321 @@
+//! Consistent hashing over a ring of integer tokens.
+//!
+//! Each node of a [`HashRing`] holds one or more tokens. A node owns every key
+//! going back down the ring from each of its tokens to the previous token, so
+//! that a token at `t` preceded by a token at `p` owns the arc `(p, t]`. A ring
+//! with a single token gives its node every key.
+//!
+//! With a replication factor of `n`, a key is owned by the first `n` distinct
+//! nodes found going up the ring from the key.
+
+use crate::{Bound, Wrange, WrangeSet, WrappingInteger};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+/// A consistent-hashing ring assigning keys of type `T` to nodes of type `N`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashRing<T, N>
+where
+    T: WrappingInteger,
+{
+    tokens: BTreeMap<T, N>,
+}
+
+/// The keys a node gains and loses when a ring changes
+#[derive(Debug, PartialEq, Eq)]
+pub struct Moves<T>
+where
+    T: WrappingInteger,
+{
+    pub gained: WrangeSet<T>,
+    pub lost: WrangeSet<T>,
+}
+
+impl<T, N> Default for HashRing<T, N>
+where
+    T: WrappingInteger,
+{
+    fn default() -> Self {
+        Self {
+            tokens: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T, N> HashRing<T, N>
+where
+    T: WrappingInteger,
+    N: Clone + Ord,
+{
+    /// An empty ring, with no nodes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every token on the ring along with the node holding it, in ring order
+    pub fn tokens(&self) -> impl Iterator<Item = (&T, &N)> {
+        self.tokens.iter()
+    }
+
+    /// Every node with at least one token on the ring
+    pub fn nodes(&self) -> BTreeSet<&N> {
+        self.tokens.values().collect()
+    }
+
+    /// Give these tokens to a node, taking over any of them already held
+    /// by other nodes
+    pub fn insert(&mut self, node: N, tokens: impl IntoIterator<Item = T>) {
+        for t in tokens {
+            self.tokens.insert(t, node.clone());
+        }
+    }
+
+    /// Give a node exactly the keys in `arc`, by removing any tokens within it,
+    /// placing one of the node's tokens at its last value, and if needed a token
+    /// just before its first value for whichever node owned that key before.
+    ///
+    /// Assigning an Empty arc does nothing, and assigning a Full one leaves
+    /// this node as the only one on the ring.
+    ///
+    /// Returns whether the arc was assigned. An empty ring has no node to own
+    /// the keys outside the arc, so assigning anything but a Full arc to it
+    /// returns false and leaves the ring empty.
+    pub fn assign(&mut self, node: N, arc: &Wrange<T>) -> bool {
+        if arc.measure() == T::SIZE {
+            self.tokens.clear();
+            self.tokens.insert(T::from_offset(T::SIZE - 1), node);
+            return true;
+        }
+        let (first, last) = match arc.first_and_last() {
+            Some(ends) => ends,
+            None => return true,
+        };
+        let before = T::from_offset(first.to_offset() + T::SIZE - 1);
+        let predecessor = match self.owners_of(&before, 1).pop() {
+            Some(p) => p,
+            None => return false,
+        };
+        self.tokens.retain(|t, _| !arc.contains(t));
+        self.tokens.insert(last, node);
+        self.tokens.entry(before).or_insert(predecessor);
+        true
+    }
+
+    /// Remove all of a node's tokens, returning whether it had any. Its keys
+    /// pass to the nodes holding the next tokens up the ring.
+    pub fn remove(&mut self, node: &N) -> bool {
+        let before = self.tokens.len();
+        self.tokens.retain(|_, n| n != node);
+        self.tokens.len() != before
+    }
+
+    /// The first `replicas` distinct nodes found going up the ring from `key`,
+    /// in order, or all of them if there are fewer nodes than that
+    pub fn owners_of(&self, key: &T, replicas: usize) -> Vec<N> {
+        let mut owners: Vec<N> = Vec::with_capacity(replicas);
+        let up = self.tokens.range(key..).chain(self.tokens.range(..key));
+        for (_, n) in up {
+            if owners.len() == replicas {
+                break;
+            }
+            if !owners.contains(n) {
+                owners.push(n.clone());
+            }
+        }
+        owners
+    }
+
+    /// Every key `node` owns as one of `replicas` owners, in canonical form
+    pub fn ownership(&self, node: &N, replicas: usize) -> WrangeSet<T> {
+        let arcs: Vec<_> = arcs(self.tokens.keys().copied().collect())
+            .into_iter()
+            .filter(|(t, _)| self.owners_of(t, replicas).contains(node))
+            .map(|(_, arc)| arc)
+            .collect();
+        WrangeSet::from(arcs).canonicalized()
+    }
+
+    /// The keys each node gains and loses, with `replicas` owners per key,
+    /// when this ring is changed into `after`. Nodes with no moves are left out.
+    pub fn moves(&self, after: &Self, replicas: usize) -> BTreeMap<N, Moves<T>> {
+        // every key between two neighbouring tokens of either ring has the same
+        // owners as the upper token, both before and after
+        let mut gained: BTreeMap<N, Vec<Wrange<T>>> = BTreeMap::new();
+        let mut lost: BTreeMap<N, Vec<Wrange<T>>> = BTreeMap::new();
+        let tokens: BTreeSet<T> = self
+            .tokens
+            .keys()
+            .chain(after.tokens.keys())
+            .copied()
+            .collect();
+        for (t, arc) in arcs(tokens.into_iter().collect()) {
+            let old = self.owners_of(&t, replicas);
+            let new = after.owners_of(&t, replicas);
+            for n in new.iter().filter(|n| !old.contains(n)) {
+                gained.entry(n.clone()).or_default().push(arc);
+            }
+            for n in old.iter().filter(|n| !new.contains(n)) {
+                lost.entry(n.clone()).or_default().push(arc);
+            }
+        }
+
+        let nodes: BTreeSet<N> = gained.keys().chain(lost.keys()).cloned().collect();
+        nodes
+            .into_iter()
+            .map(|n| {
+                let moves = Moves {
+                    gained: WrangeSet::from(gained.remove(&n).unwrap_or_default()).canonicalized(),
+                    lost: WrangeSet::from(lost.remove(&n).unwrap_or_default()).canonicalized(),
+                };
+                (n, moves)
+            })
+            .collect()
+    }
+
+    /// The keys which move if `node` joins the ring with these tokens
+    pub fn moves_on_join(
+        &self,
+        node: N,
+        tokens: impl IntoIterator<Item = T>,
+        replicas: usize,
+    ) -> BTreeMap<N, Moves<T>> {
+        let mut after = self.clone();
+        after.insert(node, tokens);
+        self.moves(&after, replicas)
+    }
+
+    /// The keys which move if `node` leaves the ring
+    pub fn moves_on_leave(&self, node: &N, replicas: usize) -> BTreeMap<N, Moves<T>> {
+        let mut after = self.clone();
+        after.remove(node);
+        self.moves(&after, replicas)
+    }
+}
+
+/// Each of these tokens, taken in ring order, along with the arc reaching down
+/// from it to the previous token
+fn arcs<T: WrappingInteger>(tokens: Vec<T>) -> Vec<(T, Wrange<T>)> {
+    let previous = tokens.last().into_iter().chain(tokens.iter());
+    tokens
+        .iter()
+        .zip(previous)
+        .map(|(t, p)| {
+            let arc = if t == p {
+                Wrange::Full
+            } else {
+                Wrange::new(Bound::Exclusive(*p), Bound::Inclusive(*t))
+            };
+            (*t, arc)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bound::*;
+
+    fn ring() -> HashRing<u8, char> {
+        let mut ring = HashRing::new();
+        ring.insert('a', vec![10, 100]);
+        ring.insert('b', vec![50]);
+        ring.insert('c', vec![200]);
+        ring
+    }
+
+    #[test]
+    fn test_owners_of() {
+        let ring = ring();
+        assert_eq!(ring.owners_of(&10, 1), vec!['a']);
+        assert_eq!(ring.owners_of(&11, 1), vec!['b']);
+        assert_eq!(ring.owners_of(&150, 2), vec!['c', 'a']);
+        assert_eq!(ring.owners_of(&250, 3), vec!['a', 'b', 'c']);
+        assert_eq!(ring.owners_of(&250, 5), vec!['a', 'b', 'c']);
+        assert_eq!(HashRing::<u8, char>::new().owners_of(&0, 1), vec![]);
+    }
+
+    #[test]
+    fn test_ownership() {
+        let ring = ring();
+        assert_eq!(
+            ring.ownership(&'a', 1),
+            vec![
+                Wrange::new(Exclusive(50), Inclusive(100)),
+                Wrange::new(Exclusive(200), Inclusive(10)),
+            ]
+            .into()
+        );
+        assert_eq!(
+            ring.ownership(&'c', 2),
+            vec![Wrange::new(Exclusive(50), Inclusive(200))].into()
+        );
+
+        let mut single = HashRing::new();
+        single.insert('a', vec![7u8]);
+        assert_eq!(single.ownership(&'a', 1), vec![Wrange::Full].into());
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut ring = ring();
+        assert!(ring.assign('d', &Wrange::new_inclusive(20, 60)));
+        assert_eq!(
+            ring.tokens().map(|(t, n)| (*t, *n)).collect::<Vec<_>>(),
+            vec![(10, 'a'), (19, 'b'), (60, 'd'), (100, 'a'), (200, 'c')]
+        );
+        assert_eq!(
+            ring.ownership(&'d', 1),
+            vec![Wrange::new(Exclusive(19), Inclusive(60))].into()
+        );
+
+        assert!(ring.assign('e', &Wrange::Full));
+        assert_eq!(ring.nodes(), vec![&'e'].into_iter().collect());
+    }
+
+    #[test]
+    fn test_assign_to_empty_ring() {
+        let mut ring = HashRing::<u8, char>::new();
+        assert!(!ring.assign('a', &Wrange::new_inclusive(20, 60)));
+        assert_eq!(ring, HashRing::new());
+        assert!(ring.ownership(&'a', 1).is_empty());
+
+        assert!(ring.assign('a', &Wrange::Full));
+        assert!(ring.assign('b', &Wrange::new_inclusive(20, 60)));
+        assert_eq!(
+            ring.ownership(&'b', 1),
+            vec![Wrange::new(Exclusive(19), Inclusive(60))].into()
+        );
+        assert_eq!(
+            ring.ownership(&'a', 1),
+            vec![Wrange::new(Exclusive(60), Inclusive(19))].into()
+        );
+    }
+
+    #[test]
+    fn test_moves() {
+        let ring = ring();
+        let moves = ring.moves_on_join('d', vec![150], 1);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(
+            moves[&'d'],
+            Moves {
+                gained: vec![Wrange::new(Exclusive(100), Inclusive(150))].into(),
+                lost: vec![].into(),
+            }
+        );
+        assert_eq!(
+            moves[&'c'],
+            Moves {
+                gained: vec![].into(),
+                lost: vec![Wrange::new(Exclusive(100), Inclusive(150))].into(),
+            }
+        );
+
+        let moves = ring.moves_on_leave(&'b', 1);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[&'b'].lost, ring.ownership(&'b', 1));
+        assert_eq!(moves[&'a'].gained, ring.ownership(&'b', 1));
+
+        assert!(ring.moves(&ring, 2).is_empty());
+    }
+}
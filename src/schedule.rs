@@ -0,0 +1,354 @@
+//! Recurring schedules, as ranges over the time of day or the day of the week.
+//!
+//! [`SecondOfDay`] and [`Weekday`] are rings of 86400 and 7 values which wrap
+//! at midnight and at the end of the week, so that a window like 22:00–02:00
+//! or Fri–Mon is simply a Divergent range. Both types implement
+//! [`WrappingInteger`], so all the integer-domain operations apply to them,
+//! and both have a text form, so ranges of them render as e.g. `[22:00, 02:00)`.
+//!
+//! With the `chrono` or `time` feature enabled, times of day and weekdays from
+//! those crates convert into these types, so that an instant can be tested
+//! against a window with e.g. `window.contains(&now.time().into())`.
+
+use crate::{Bound, Wrange, WrappingInteger};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// A time of day, to the second, counted up from midnight
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SecondOfDay(u32);
+
+impl SecondOfDay {
+    pub const MIDNIGHT: Self = Self(0);
+
+    /// The given time of day, or None if any part is out of range
+    pub fn new(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 && second < 60 {
+            Some(Self(hour * 3600 + minute * 60 + second))
+        } else {
+            None
+        }
+    }
+
+    /// The time this many seconds after midnight, or None if that is
+    /// a day or more
+    pub fn from_seconds(seconds: u32) -> Option<Self> {
+        if seconds < SECONDS_PER_DAY {
+            Some(Self(seconds))
+        } else {
+            None
+        }
+    }
+
+    /// The number of seconds since midnight
+    pub fn seconds(&self) -> u32 {
+        self.0
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.0 / 3600
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.0 / 60 % 60
+    }
+
+    pub fn second(&self) -> u32 {
+        self.0 % 60
+    }
+}
+
+impl WrappingInteger for SecondOfDay {
+    const SIZE: u128 = SECONDS_PER_DAY as u128;
+
+    fn to_offset(self) -> u128 {
+        self.0 as u128
+    }
+
+    fn from_offset(offset: u128) -> Self {
+        Self((offset % Self::SIZE) as u32)
+    }
+
+    fn wrapping_add(self, rhs: Self) -> Self {
+        Self((self.0 + rhs.0) % SECONDS_PER_DAY)
+    }
+}
+
+/// Written as `HH:MM`, or `HH:MM:SS` if the seconds are not zero
+impl Display for SecondOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())?;
+        if self.second() != 0 {
+            write!(f, ":{:02}", self.second())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SecondOfDay {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |p: &str| {
+            if p.is_empty() || p.len() > 2 || !p.bytes().all(|b| b.is_ascii_digit()) {
+                None
+            } else {
+                p.parse().ok()
+            }
+        };
+        let mut parts = s.split(':').map(number);
+        let time = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(h)), Some(Some(m)), None, None) => Self::new(h, m, 0),
+            (Some(Some(h)), Some(Some(m)), Some(Some(s)), None) => Self::new(h, m, s),
+            _ => None,
+        };
+        time.ok_or(ParseScheduleError::TimeOfDay)
+    }
+}
+
+/// A day of the week, with weeks starting on Monday
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// The full English name of this day
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Monday => "Monday",
+            Self::Tuesday => "Tuesday",
+            Self::Wednesday => "Wednesday",
+            Self::Thursday => "Thursday",
+            Self::Friday => "Friday",
+            Self::Saturday => "Saturday",
+            Self::Sunday => "Sunday",
+        }
+    }
+}
+
+/// Days are offset from Monday, so adding Tuesday moves on by one day
+impl WrappingInteger for Weekday {
+    const SIZE: u128 = 7;
+
+    fn to_offset(self) -> u128 {
+        self as u128
+    }
+
+    fn from_offset(offset: u128) -> Self {
+        Self::ALL[(offset % Self::SIZE) as usize]
+    }
+
+    fn wrapping_add(self, rhs: Self) -> Self {
+        Self::from_offset(self.to_offset() + rhs.to_offset())
+    }
+}
+
+/// Written as the first three letters of the day's name
+impl Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.name()[..3])
+    }
+}
+
+/// Accepts either the full name or its first three letters, in any case
+impl FromStr for Weekday {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|d| s.eq_ignore_ascii_case(d.name()) || s.eq_ignore_ascii_case(&d.name()[..3]))
+            .copied()
+            .ok_or(ParseScheduleError::Weekday)
+    }
+}
+
+/// An error parsing a time of day or a day of the week
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseScheduleError {
+    TimeOfDay,
+    Weekday,
+}
+
+impl Display for ParseScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimeOfDay => write!(f, "expected a time of day as HH:MM or HH:MM:SS"),
+            Self::Weekday => write!(f, "expected a day of the week"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseScheduleError {}
+
+/// The daily window from `start` up to but not including `end`, wrapping past
+/// midnight if `end` comes first. A window which ends when it starts lasts
+/// all day.
+pub fn time_window(start: SecondOfDay, end: SecondOfDay) -> Wrange<SecondOfDay> {
+    if start == end {
+        Wrange::Full
+    } else {
+        Wrange::new(Bound::Inclusive(start), Bound::Exclusive(end))
+    }
+}
+
+/// The weekly window of days from `first` to `last`, both included, wrapping
+/// past Sunday if `last` comes first
+pub fn day_window(first: Weekday, last: Weekday) -> Wrange<Weekday> {
+    Wrange::new_inclusive(first, last)
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use super::*;
+    use chrono::Timelike;
+
+    impl From<chrono::NaiveTime> for SecondOfDay {
+        fn from(t: chrono::NaiveTime) -> Self {
+            Self(t.num_seconds_from_midnight())
+        }
+    }
+
+    impl From<chrono::Weekday> for Weekday {
+        fn from(d: chrono::Weekday) -> Self {
+            Self::from_offset(d.num_days_from_monday() as u128)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_support {
+    use super::*;
+
+    impl From<time::Time> for SecondOfDay {
+        fn from(t: time::Time) -> Self {
+            Self(t.hour() as u32 * 3600 + t.minute() as u32 * 60 + t.second() as u32)
+        }
+    }
+
+    impl From<time::Weekday> for Weekday {
+        fn from(d: time::Weekday) -> Self {
+            Self::from_offset(d.number_days_from_monday() as u128)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::WrangeSet;
+    use Weekday::*;
+
+    fn at(hour: u32, minute: u32) -> SecondOfDay {
+        SecondOfDay::new(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_time_window() {
+        let night = time_window(at(22, 0), at(2, 0));
+        assert!(matches!(night, Wrange::Divergent(_)));
+        assert!(night.contains(&at(23, 59)));
+        assert!(night.contains(&SecondOfDay::MIDNIGHT));
+        assert!(night.contains(&at(1, 59)));
+        assert!(!night.contains(&at(2, 0)));
+        assert!(!night.contains(&at(12, 0)));
+        assert_eq!(night.measure(), 4 * 3600);
+        assert_eq!(time_window(at(9, 0), at(9, 0)), Wrange::Full);
+    }
+
+    #[test]
+    fn test_day_window() {
+        let weekend = day_window(Friday, Monday);
+        assert!(weekend.contains(&Saturday));
+        assert!(weekend.contains(&Monday));
+        assert!(!weekend.contains(&Wednesday));
+        assert_eq!(weekend.measure(), 4);
+        assert_eq!(weekend.to_string(), "[Fri, Mon]");
+    }
+
+    #[test]
+    fn test_intersect_schedules() {
+        let ours = time_window(at(22, 0), at(2, 0));
+        let theirs = time_window(at(1, 0), at(6, 30));
+        let both = Wrange::intersection(&ours, &theirs);
+        assert_eq!(both, WrangeSet::from(vec![time_window(at(1, 0), at(2, 0))]));
+        assert_eq!(both.to_string(), "[01:00, 02:00)");
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!("22:00".parse(), Ok(at(22, 0)));
+        assert_eq!(
+            "7:05:09".parse(),
+            SecondOfDay::new(7, 5, 9).ok_or(ParseScheduleError::TimeOfDay)
+        );
+        assert_eq!(
+            "24:00".parse::<SecondOfDay>(),
+            Err(ParseScheduleError::TimeOfDay)
+        );
+        assert_eq!(
+            "12".parse::<SecondOfDay>(),
+            Err(ParseScheduleError::TimeOfDay)
+        );
+        assert_eq!(
+            "+1:00".parse::<SecondOfDay>(),
+            Err(ParseScheduleError::TimeOfDay)
+        );
+        assert_eq!(SecondOfDay::new(7, 5, 9).unwrap().to_string(), "07:05:09");
+
+        assert_eq!("fri".parse(), Ok(Friday));
+        assert_eq!("Sunday".parse(), Ok(Sunday));
+        assert_eq!(
+            "Sundae".parse::<Weekday>(),
+            Err(ParseScheduleError::Weekday)
+        );
+
+        let w: Wrange<SecondOfDay> = "[22:00, 02:00)".parse().unwrap();
+        assert_eq!(w, time_window(at(22, 0), at(2, 0)));
+    }
+
+    #[test]
+    fn test_wrapping() {
+        assert_eq!(Sunday.wrapping_add(Tuesday), Monday);
+        assert_eq!(
+            time_window(at(22, 0), at(2, 0)).shift_by(at(3, 0)),
+            time_window(at(1, 0), at(5, 0))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        let t = chrono::NaiveTime::from_hms_opt(23, 30, 15).unwrap();
+        assert_eq!(SecondOfDay::from(t), SecondOfDay::new(23, 30, 15).unwrap());
+        assert_eq!(Weekday::from(chrono::Weekday::Sun), Sunday);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() {
+        let t = time::Time::from_hms(23, 30, 15).unwrap();
+        assert_eq!(SecondOfDay::from(t), SecondOfDay::new(23, 30, 15).unwrap());
+        assert_eq!(Weekday::from(time::Weekday::Sunday), Sunday);
+    }
+}
@@ -0,0 +1,179 @@
+//! Decomposition of Wranges into non-wrapping segments, which are easier
+//! to compare with one another.
+
+use crate::{Bound, Bounds, Wrange};
+use core::cmp::Ordering;
+
+/// A non-wrapping interval, where `None` stands for an unbounded end.
+/// Every Wrange decomposes into at most two of these: a Divergent range is
+/// the union of a segment unbounded below and a segment unbounded above.
+#[derive(Debug)]
+pub(crate) struct Segment<'a, T> {
+    pub lo: Option<&'a Bound<T>>,
+    pub hi: Option<&'a Bound<T>>,
+}
+
+impl<'a, T: Ord + Clone> Segment<'a, T> {
+    pub fn is_empty(&self) -> bool {
+        match (self.lo, self.hi) {
+            (Some(lo), Some(hi)) => match lo.inner().cmp(hi.inner()) {
+                Ordering::Less => false,
+                Ordering::Equal => !matches!((lo, hi), (Bound::Inclusive(_), Bound::Inclusive(_))),
+                Ordering::Greater => true,
+            },
+            _ => false,
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            lo: if cmp_lo(self.lo, other.lo) == Ordering::Less {
+                other.lo
+            } else {
+                self.lo
+            },
+            hi: if cmp_hi(self.hi, other.hi) == Ordering::Greater {
+                other.hi
+            } else {
+                self.hi
+            },
+        }
+    }
+
+    /// Whether a segment starting no earlier than this one starts before this
+    /// one ends, or right at its end, so that the two can be merged
+    #[cfg(feature = "alloc")]
+    pub fn reaches(&self, next: &Self) -> bool {
+        match (self.hi, next.lo) {
+            (Some(hi), Some(lo)) => match lo.inner().cmp(hi.inner()) {
+                Ordering::Less => true,
+                Ordering::Equal => !matches!((hi, lo), (Bound::Exclusive(_), Bound::Exclusive(_))),
+                Ordering::Greater => false,
+            },
+            _ => true,
+        }
+    }
+
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        cmp_lo(other.lo, self.lo) != Ordering::Greater
+            && cmp_hi(self.hi, other.hi) != Ordering::Greater
+    }
+
+    /// Whether this segment contains the first points of a range with this start
+    pub fn covers_start(&self, start: &Bound<T>) -> bool {
+        match start {
+            Bound::Inclusive(x) => self.covers_point(x),
+            Bound::Exclusive(x) => {
+                self.lo.map_or(true, |lo| lo.inner() <= x)
+                    && self.hi.map_or(true, |hi| hi.inner() > x)
+            }
+        }
+    }
+
+    /// Whether this segment contains the last points of a range with this end
+    pub fn covers_end(&self, end: &Bound<T>) -> bool {
+        match end {
+            Bound::Inclusive(x) => self.covers_point(x),
+            Bound::Exclusive(x) => {
+                self.lo.map_or(true, |lo| lo.inner() < x)
+                    && self.hi.map_or(true, |hi| hi.inner() >= x)
+            }
+        }
+    }
+
+    pub fn covers_point(&self, x: &T) -> bool {
+        let above = |lo: &Bound<T>| match lo {
+            Bound::Inclusive(lo) => lo <= x,
+            Bound::Exclusive(lo) => lo < x,
+        };
+        let below = |hi: &Bound<T>| match hi {
+            Bound::Inclusive(hi) => x <= hi,
+            Bound::Exclusive(hi) => x < hi,
+        };
+        self.lo.map_or(true, above) && self.hi.map_or(true, below)
+    }
+}
+
+/// Compare two lower bounds: an unbounded end comes first, and an inclusive
+/// bound starts before an exclusive bound at the same position.
+pub(crate) fn cmp_lo<T: Ord + Clone>(a: Option<&Bound<T>>, b: Option<&Bound<T>>) -> Ordering {
+    use Bound::*;
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => a.inner().cmp(b.inner()).then(match (a, b) {
+            (Inclusive(_), Exclusive(_)) => Ordering::Less,
+            (Exclusive(_), Inclusive(_)) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }),
+    }
+}
+
+/// Compare two upper bounds: an unbounded end comes last, and an exclusive
+/// bound ends before an inclusive bound at the same position.
+pub(crate) fn cmp_hi<T: Ord + Clone>(a: Option<&Bound<T>>, b: Option<&Bound<T>>) -> Ordering {
+    use Bound::*;
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.inner().cmp(b.inner()).then(match (a, b) {
+            (Exclusive(_), Inclusive(_)) => Ordering::Less,
+            (Inclusive(_), Exclusive(_)) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }),
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: Ord + Clone,
+{
+    /// The start and end of this range as they would be after normalization,
+    /// or None if the range normalizes to Empty or Full.
+    pub(crate) fn edges(&self) -> Option<(&Bound<T>, &Bound<T>)> {
+        use Bound::*;
+        use Wrange::*;
+        match self {
+            Empty | Full => None,
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) if a.overlaps(b) => {
+                match (self, a, b) {
+                    (Convergent(_), Exclusive(_), Exclusive(_)) => None,
+                    (Convergent(_), Exclusive(_), i @ Inclusive(_))
+                    | (Convergent(_), i @ Inclusive(_), _) => Some((i, i)),
+                    (Divergent(_), Exclusive(_), Exclusive(_)) => Some((a, b)),
+                    _ => None,
+                }
+            }
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) => Some((a, b)),
+        }
+    }
+
+    pub(crate) fn segments(&self) -> impl Iterator<Item = Segment<'_, T>> {
+        let segments = match (self, self.edges()) {
+            (Wrange::Convergent(_), Some((a, b))) => [
+                Some(Segment {
+                    lo: Some(a),
+                    hi: Some(b),
+                }),
+                None,
+            ],
+            (Wrange::Divergent(_), Some((a, b))) => [
+                Some(Segment {
+                    lo: None,
+                    hi: Some(b),
+                }),
+                Some(Segment {
+                    lo: Some(a),
+                    hi: None,
+                }),
+            ],
+            (Wrange::Full, _) | (Wrange::Divergent(_), None) => {
+                [Some(Segment { lo: None, hi: None }), None]
+            }
+            _ => [None, None],
+        };
+        IntoIterator::into_iter(segments).flatten()
+    }
+}
@@ -0,0 +1,158 @@
+//! Windows of sequence numbers which wrap, ordered by the serial number
+//! arithmetic of RFC 1982.
+//!
+//! Under serial arithmetic, `a` comes before `b` if going up the ring from `a`
+//! reaches `b` in fewer than half the values of the domain. Values exactly half
+//! the ring apart are not ordered at all.
+
+use crate::{Bound, Bounds, Wrange, WrappingInteger};
+use core::cmp::Ordering;
+
+/// Compare two serial numbers as described in RFC 1982, returning None if
+/// they are exactly half the ring apart
+pub fn serial_cmp<T: WrappingInteger>(a: &T, b: &T) -> Option<Ordering> {
+    let half = T::SIZE / 2;
+    let steps = (b.to_offset() + T::SIZE - a.to_offset()) % T::SIZE;
+    match steps {
+        0 => Some(Ordering::Equal),
+        s if s < half => Some(Ordering::Less),
+        s if s > half => Some(Ordering::Greater),
+        _ => None,
+    }
+}
+
+/// A window of sequence numbers running up the ring from its start to its end,
+/// where the start never comes after the end in serial order. As with Wrange,
+/// colocated endpoints of mixed inclusivity contain that one value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SerialWrange<T>
+where
+    T: WrappingInteger,
+{
+    bounds: Bounds<T>,
+}
+
+impl<T> SerialWrange<T>
+where
+    T: WrappingInteger,
+{
+    /// The window from `start` up to `end`, or None if `end` comes before
+    /// `start` in serial order, or they are exactly half the ring apart.
+    pub fn new(start: Bound<T>, end: Bound<T>) -> Option<Self> {
+        match serial_cmp(start.inner(), end.inner()) {
+            Some(Ordering::Less) | Some(Ordering::Equal) => Some(Self {
+                bounds: Bounds(start, end),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn start(&self) -> &Bound<T> {
+        &self.bounds.0
+    }
+
+    pub fn end(&self) -> &Bound<T> {
+        &self.bounds.1
+    }
+
+    /// The same values as a plain Wrange
+    pub fn to_wrange(&self) -> Wrange<T> {
+        Wrange::new(self.bounds.0, self.bounds.1)
+    }
+
+    /// Whether this window contains the sequence number `x`
+    pub fn contains(&self, x: &T) -> bool {
+        self.to_wrange().contains(x)
+    }
+
+    /// Slide the window up the ring by `n`, wrapping past MAX
+    pub fn advance(&mut self, n: T) {
+        let Bounds(a, b) = &self.bounds;
+        self.bounds = Bounds(a.map(|x| x.wrapping_add(n)), b.map(|x| x.wrapping_add(n)));
+    }
+
+    /// The sequence numbers in both windows, or None if there are none.
+    ///
+    /// Since each window spans less than half the ring, they can only
+    /// overlap in a single window.
+    pub fn intersection(a: &Self, b: &Self) -> Option<Self> {
+        Wrange::intersect_pair(&a.to_wrange(), &b.to_wrange())
+            .into_iter()
+            .find(|r| r.first_and_last().is_some())
+            .and_then(|r| match r.normalized() {
+                Wrange::Convergent(Bounds(a, b)) | Wrange::Divergent(Bounds(a, b)) => {
+                    Self::new(a, b)
+                }
+                Wrange::Empty | Wrange::Full => None,
+            })
+    }
+}
+
+impl<T> From<SerialWrange<T>> for Wrange<T>
+where
+    T: WrappingInteger,
+{
+    fn from(w: SerialWrange<T>) -> Self {
+        w.to_wrange()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bound::*;
+
+    #[test]
+    fn test_serial_cmp() {
+        use Ordering::*;
+        assert_eq!(serial_cmp(&1u16, &2), Some(Less));
+        assert_eq!(serial_cmp(&65535u16, &0), Some(Less));
+        assert_eq!(serial_cmp(&0u16, &65535), Some(Greater));
+        assert_eq!(serial_cmp(&7u16, &7), Some(Equal));
+        assert_eq!(serial_cmp(&0u16, &32767), Some(Less));
+        assert_eq!(serial_cmp(&0u16, &32768), None);
+        assert_eq!(serial_cmp(&0u16, &32769), Some(Greater));
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SerialWrange::new(Inclusive(65530u16), Exclusive(10)).is_some());
+        assert!(SerialWrange::new(Inclusive(10u16), Exclusive(65530)).is_none());
+        assert!(SerialWrange::new(Inclusive(0u16), Exclusive(32768)).is_none());
+        assert!(SerialWrange::new(Inclusive(5u16), Exclusive(5)).is_some());
+    }
+
+    #[test]
+    fn test_contains_and_advance() {
+        let mut w = SerialWrange::new(Inclusive(u32::MAX - 2), Exclusive(3)).unwrap();
+        assert!(w.contains(&u32::MAX));
+        assert!(w.contains(&0));
+        assert!(!w.contains(&3));
+        assert!(!w.contains(&(u32::MAX - 3)));
+
+        w.advance(10);
+        assert_eq!(w.start(), &Inclusive(7));
+        assert_eq!(w.end(), &Exclusive(13));
+        assert!(!w.contains(&0));
+        assert!(w.contains(&12));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = SerialWrange::new(Inclusive(65500u16), Exclusive(100)).unwrap();
+        let b = SerialWrange::new(Inclusive(50u16), Exclusive(200)).unwrap();
+        assert_eq!(
+            SerialWrange::intersection(&a, &b),
+            SerialWrange::new(Inclusive(50), Exclusive(100))
+        );
+
+        let c = SerialWrange::new(Inclusive(65520u16), Inclusive(65535)).unwrap();
+        assert_eq!(
+            SerialWrange::intersection(&a, &c),
+            SerialWrange::new(Inclusive(65520), Inclusive(65535))
+        );
+
+        let d = SerialWrange::new(Inclusive(100u16), Exclusive(200)).unwrap();
+        assert_eq!(SerialWrange::intersection(&a, &d), None);
+    }
+}
@@ -0,0 +1,240 @@
+//! Splitting a range into contiguous pieces which exactly tile it.
+
+use crate::{Bound, Bounds, Wrange, WrappingInteger};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl<T> Wrange<T>
+where
+    T: Ord + Clone,
+{
+    /// Split this range in two at `x`, so that `x` becomes the inclusive start
+    /// of the second piece and the exclusive end of the first.
+    ///
+    /// If `x` does not lie within the range, or already is its first point,
+    /// there is nothing to split and the range is returned as a single piece.
+    /// Likewise Full is returned as a single piece, since splitting a full ring
+    /// at one point only moves where it starts.
+    pub fn split_at(&self, x: &T) -> Vec<Self> {
+        use Bound::*;
+        use Wrange::*;
+        match self.clone().normalized() {
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b))
+                if self.contains(x) && a.inner() != x =>
+            {
+                vec![
+                    Self::new(a, Exclusive(x.clone())),
+                    Self::new(Inclusive(x.clone()), b),
+                ]
+            }
+            _ => vec![self.clone()],
+        }
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: WrappingInteger,
+{
+    /// Split this range into `n` contiguous pieces which exactly tile it,
+    /// in order from start to end. The pieces contain as close to equal
+    /// numbers of values as possible. If the range contains fewer than `n`
+    /// values, some of the pieces are Empty.
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_into(&self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "cannot split a range into zero pieces");
+        self.split_weighted(&vec![1; n])
+    }
+
+    /// Split this range into contiguous pieces which exactly tile it,
+    /// in order from start to end, with each piece containing a number of
+    /// values proportional to its weight (rounded down, with any remainder
+    /// going to later pieces). Pieces which would contain no values are Empty.
+    ///
+    /// Panics if there are no weights, or they are all zero.
+    pub fn split_weighted(&self, weights: &[u32]) -> Vec<Self> {
+        use Bound::*;
+        use Wrange::*;
+        let total: u128 = weights.iter().map(|w| *w as u128).sum();
+        assert!(total > 0, "cannot split a range by zero total weight");
+
+        let (start, end, first) = match self.normalized() {
+            _ if weights.len() == 1 => return vec![*self],
+            Full => (
+                Inclusive(T::from_offset(0)),
+                Inclusive(T::from_offset(T::SIZE - 1)),
+                0,
+            ),
+            Convergent(Bounds(a, b)) | Divergent(Bounds(a, b)) => match self.first_and_last() {
+                Some((first, _)) => (a, b, first.to_offset()),
+                None => {
+                    // nothing to share out: the first piece gets everything
+                    let mut pieces = vec![Empty; weights.len()];
+                    pieces[0] = *self;
+                    return pieces;
+                }
+            },
+            Empty => return vec![Empty; weights.len()],
+        };
+
+        let measure = self.measure();
+        let mut cumulative = 0;
+        // the offset of the first value of each piece, followed by
+        // the offset just past the last value of the last piece
+        let mut boundaries = vec![first];
+        for w in weights {
+            cumulative += *w as u128;
+            boundaries.push(first + measure * cumulative / total);
+        }
+
+        let last = weights.len() - 1;
+        boundaries
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| {
+                if w[0] == w[1] {
+                    Empty
+                } else if w[1] - w[0] == T::SIZE {
+                    Full
+                } else {
+                    let lo = if i == 0 {
+                        start
+                    } else {
+                        Inclusive(T::from_offset(w[0]))
+                    };
+                    let hi = if i == last {
+                        end
+                    } else {
+                        Exclusive(T::from_offset(w[1]))
+                    };
+                    if lo.overlaps(&hi) && w[1] - w[0] > 1 {
+                        // a piece reaching all the way around to its own start
+                        Divergent(Bounds(lo, hi))
+                    } else {
+                        Self::new(lo, hi)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).split_at(&15),
+            vec![
+                Wrange::new(Inclusive(10), Exclusive(15)),
+                Wrange::new(Inclusive(15), Inclusive(20))
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).split_at(&20),
+            vec![
+                Wrange::new(Inclusive(10), Exclusive(20)),
+                Wrange::new(Inclusive(20), Inclusive(20))
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).split_at(&10),
+            vec![Wrange::new_inclusive(10, 20)]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(10, 20).split_at(&30),
+            vec![Wrange::new_inclusive(10, 20)]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 10).split_at(&252),
+            vec![
+                Convergent(Bounds(Inclusive(250), Exclusive(252))),
+                Divergent(Bounds(Inclusive(252), Inclusive(10)))
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 10).split_at(&2),
+            vec![
+                Divergent(Bounds(Inclusive(250), Exclusive(2))),
+                Convergent(Bounds(Inclusive(2), Inclusive(10)))
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(250, 10).split_at(&0),
+            vec![
+                Divergent(Bounds(Inclusive(250), Exclusive(0))),
+                Convergent(Bounds(Inclusive(0), Inclusive(10)))
+            ]
+        );
+        assert_eq!(Wrange::<u8>::Full.split_at(&3), vec![Full]);
+        assert_eq!(Wrange::<u8>::Empty.split_at(&3), vec![Empty]);
+    }
+
+    #[test]
+    fn test_split_into() {
+        use Bound::*;
+        use Wrange::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(0, 9).split_into(3),
+            vec![
+                Wrange::new(Inclusive(0), Exclusive(3)),
+                Wrange::new(Inclusive(3), Exclusive(6)),
+                Wrange::new(Inclusive(6), Inclusive(9)),
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::Full.split_into(4),
+            vec![
+                Wrange::new(Inclusive(0), Exclusive(64)),
+                Wrange::new(Inclusive(64), Exclusive(128)),
+                Wrange::new(Inclusive(128), Exclusive(192)),
+                Wrange::new(Inclusive(192), Inclusive(255)),
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new(Exclusive(250), Exclusive(10)).split_into(3),
+            vec![
+                Divergent(Bounds(Exclusive(250), Exclusive(0))),
+                Wrange::new(Inclusive(0), Exclusive(5)),
+                Wrange::new(Inclusive(5), Exclusive(10)),
+            ]
+        );
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(5, 6).split_into(3),
+            vec![
+                Empty,
+                Wrange::new(Inclusive(5), Exclusive(6)),
+                Wrange::new(Inclusive(6), Inclusive(6))
+            ]
+        );
+        assert_eq!(Wrange::<u8>::Full.split_into(1), vec![Full]);
+        assert_eq!(Wrange::<u8>::Empty.split_into(2), vec![Empty, Empty]);
+    }
+
+    #[test]
+    fn test_split_weighted() {
+        use Bound::*;
+
+        assert_eq!(
+            Wrange::<u8>::new_inclusive(0, 9).split_weighted(&[1, 0, 4]),
+            vec![
+                Wrange::new(Inclusive(0), Exclusive(2)),
+                Wrange::Empty,
+                Wrange::new(Inclusive(2), Inclusive(9)),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_into_zero() {
+        Wrange::<u8>::Full.split_into(0);
+    }
+}
@@ -0,0 +1,189 @@
+//! Drawing of WrangeSets as circular diagrams, in which each set is a ring
+//! of arcs around the same circle.
+//!
+//! The domain runs clockwise from the top of the circle, which is marked
+//! with a tick, and wraps back around to it, so that Divergent ranges are
+//! simply arcs crossing the tick. Each endpoint is marked with a dot, filled
+//! if the bound is Inclusive and hollow if it is Exclusive.
+
+use crate::{Bound, Bounds, Wrange, WrangeSet, WrappingInteger};
+use std::f64::consts::TAU;
+use std::fmt::Write;
+use std::hash::Hash;
+
+/// The radius of the innermost ring
+const INNER: f64 = 40.0;
+/// The distance between neighbouring rings
+const GAP: f64 = 16.0;
+/// The space around the outermost ring
+const MARGIN: f64 = 16.0;
+const STROKE: f64 = 6.0;
+const DOT: f64 = 4.0;
+const TRACK: &str = "#e0e0e0";
+const PALETTE: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#9467bd"];
+
+/// Draw each set as a ring, from the innermost outwards, with every value of
+/// the integer type placed evenly around the circle.
+pub fn render<T: WrappingInteger>(sets: &[WrangeSet<T>]) -> String {
+    render_with(sets, |x| x.to_offset() as f64 / T::SIZE as f64)
+}
+
+/// Draw each set as a ring, from the innermost outwards, placing each value
+/// at the fraction of a turn given by `turn`, which should lie in `[0, 1)`
+/// and increase with the value.
+///
+/// For angles in some [`Angle`](crate::Angle) domain, that is the angle
+/// divided by the period.
+pub fn render_with<T, F>(sets: &[WrangeSet<T>], turn: F) -> String
+where
+    T: Ord + Hash + Clone,
+    F: Fn(&T) -> f64,
+{
+    let outer = INNER + GAP * sets.len().saturating_sub(1) as f64;
+    let size = 2.0 * (outer + MARGIN);
+    let c = size / 2.0;
+    let mut svg = String::new();
+    // writing to a String cannot fail
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{s}" height="{s}" viewBox="0 0 {s} {s}">"#,
+        s = num(size)
+    );
+    let _ = writeln!(
+        svg,
+        r##"  <line x1="{c}" y1="{}" x2="{c}" y2="{}" stroke="#999" stroke-width="1"/>"##,
+        num(MARGIN / 4.0),
+        num(c - INNER + STROKE),
+        c = num(c)
+    );
+    for (k, set) in sets.iter().enumerate() {
+        let ring = Ring {
+            c,
+            r: INNER + GAP * k as f64,
+            color: PALETTE[k % PALETTE.len()],
+        };
+        let _ = writeln!(svg, r#"  <g data-ring="{}">"#, k);
+        ring.circle(&mut svg, TRACK);
+        for w in set.to_canonical_vec() {
+            ring.wrange(&mut svg, &w, &turn);
+        }
+        svg.push_str("  </g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A number rounded to two decimal places, without a negative zero
+fn num(x: f64) -> String {
+    format!("{:.2}", (x * 100.0).round() / 100.0 + 0.0)
+}
+
+/// One ring of the diagram, as a circle of radius `r` around `(c, c)`
+struct Ring {
+    c: f64,
+    r: f64,
+    color: &'static str,
+}
+
+impl Ring {
+    /// The point at this fraction of a turn clockwise from the top
+    fn point(&self, turn: f64) -> (String, String) {
+        let (sin, cos) = (turn * TAU).sin_cos();
+        (num(self.c + self.r * sin), num(self.c - self.r * cos))
+    }
+
+    fn circle(&self, svg: &mut String, color: &str) {
+        let _ = writeln!(
+            svg,
+            r#"    <circle cx="{c}" cy="{c}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            num(self.r),
+            color,
+            num(STROKE),
+            c = num(self.c)
+        );
+    }
+
+    fn arc(&self, svg: &mut String, from: f64, span: f64) {
+        let (x0, y0) = self.point(from);
+        let (x1, y1) = self.point(from + span);
+        let large = if span > 0.5 { 1 } else { 0 };
+        let _ = writeln!(
+            svg,
+            r#"    <path d="M {} {} A {r} {r} 0 {} 1 {} {}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            x0,
+            y0,
+            large,
+            x1,
+            y1,
+            self.color,
+            num(STROKE),
+            r = num(self.r)
+        );
+    }
+
+    fn dot<T>(&self, svg: &mut String, b: &Bound<T>, turn: f64) {
+        let (x, y) = self.point(turn);
+        let fill = match b {
+            Bound::Inclusive(_) => self.color,
+            Bound::Exclusive(_) => "#fff",
+        };
+        let _ = writeln!(
+            svg,
+            r#"    <circle cx="{}" cy="{}" r="{}" fill="{}" stroke="{}" stroke-width="2"/>"#,
+            x,
+            y,
+            num(DOT),
+            fill,
+            self.color
+        );
+    }
+
+    fn wrange<T: PartialEq, F: Fn(&T) -> f64>(&self, svg: &mut String, w: &Wrange<T>, turn: F) {
+        let (a, b, divergent) = match w {
+            Wrange::Empty => return,
+            Wrange::Full => return self.circle(svg, self.color),
+            Wrange::Convergent(Bounds(a, b)) => (a, b, false),
+            Wrange::Divergent(Bounds(a, b)) => (a, b, true),
+        };
+        let (from, to) = (turn(a.inner()), turn(b.inner()));
+        let span = if divergent {
+            1.0 - from + to
+        } else {
+            to - from
+        };
+        if span >= 1.0 {
+            // an arc cannot start and end at the same point, but a circle can
+            self.circle(svg, self.color);
+        } else if span > 0.0 {
+            self.arc(svg, from, span);
+        }
+        self.dot(svg, a, from);
+        if a != b {
+            self.dot(svg, b, to);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_num() {
+        assert_eq!(num(1.0), "1.00");
+        assert_eq!(num(-0.001), "0.00");
+        assert_eq!(num(2.345_678), "2.35");
+    }
+
+    #[test]
+    fn test_point() {
+        let ring = Ring {
+            c: 50.0,
+            r: 40.0,
+            color: PALETTE[0],
+        };
+        assert_eq!(ring.point(0.0), ("50.00".into(), "10.00".into()));
+        assert_eq!(ring.point(0.25), ("90.00".into(), "50.00".into()));
+        assert_eq!(ring.point(0.5), ("50.00".into(), "90.00".into()));
+    }
+}
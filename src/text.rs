@@ -0,0 +1,499 @@
+//! A human-readable text syntax for bounds, ranges and sets of ranges,
+//! via `Display` and `FromStr`.
+//!
+//! Ranges are written in interval notation, e.g. `[10, 20)` or `(250, 5]`,
+//! where a square bracket marks an inclusive bound and a round bracket an
+//! exclusive one. Whether a range is Convergent or Divergent is inferred from
+//! the order of its endpoints, exactly as in [`Wrange::new`]. The one exception
+//! is a Divergent range whose endpoints are colocated, which is marked with a
+//! leading `~`, as in `~(5, 5)`. Empty is written `∅` (or `empty`), and Full
+//! is written `full`.
+//!
+//! A bound on its own is written as its value in the appropriate brackets,
+//! e.g. `[10]` or `(10)`.
+//!
+//! A set of ranges is written as a comma-separated list of its ranges, e.g.
+//! `[1, 5), [10, 20]`. A set with no ranges at all is written as nothing.
+
+use crate::{Bound, Bounds, Wrange, WrangeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::hash::Hash;
+use core::str::FromStr;
+
+/// An error encountered while parsing, with the byte offset in the input
+/// at which it was found
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+/// The ways in which parsing can fail
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Something other than what was expected was found
+    Expected(&'static str),
+    /// A value could not be parsed, for the given reason
+    InvalidValue(String),
+    /// A Divergent range was marked with `~`, but its endpoints are not colocated
+    MisplacedWrap,
+    /// The input continues after the end of the value
+    TrailingInput,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Expected(what) => write!(f, "expected {}", what),
+            ParseErrorKind::InvalidValue(reason) => write!(f, "invalid value: {}", reason),
+            ParseErrorKind::MisplacedWrap => {
+                write!(f, "`~` may only mark a range with colocated endpoints")
+            }
+            ParseErrorKind::TrailingInput => write!(f, "unexpected trailing input"),
+        }?;
+        write!(f, " at position {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+type ParseResult<T> = Result<T, ParseError>;
+
+fn open_bracket<T>(b: &Bound<T>) -> char {
+    match b {
+        Bound::Inclusive(_) => '[',
+        Bound::Exclusive(_) => '(',
+    }
+}
+
+fn close_bracket<T>(b: &Bound<T>) -> char {
+    match b {
+        Bound::Inclusive(_) => ']',
+        Bound::Exclusive(_) => ')',
+    }
+}
+
+impl<T: Display> Display for Bound<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x = match self {
+            Bound::Inclusive(x) | Bound::Exclusive(x) => x,
+        };
+        write!(f, "{}{}{}", open_bracket(self), x, close_bracket(self))
+    }
+}
+
+impl<T> Display for Wrange<T>
+where
+    T: PartialEq + Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wrange::Empty => write!(f, "∅"),
+            Wrange::Full => write!(f, "full"),
+            Wrange::Convergent(Bounds(a, b)) | Wrange::Divergent(Bounds(a, b)) => {
+                if let Wrange::Divergent(_) = self {
+                    if a.overlaps(b) {
+                        write!(f, "~")?;
+                    }
+                }
+                write!(
+                    f,
+                    "{}{}, {}{}",
+                    open_bracket(a),
+                    a.inner(),
+                    b.inner(),
+                    close_bracket(b)
+                )
+            }
+        }
+    }
+}
+
+/// A total order on ranges, used only to list the members of a set
+/// in a deterministic order
+fn display_order<T>(a: &Wrange<T>, b: &Wrange<T>) -> Ordering
+where
+    T: Ord + Clone,
+{
+    let key = |w: &Wrange<T>| {
+        let inclusive = |b: &Bound<T>| matches!(b, Bound::Inclusive(_));
+        match w {
+            Wrange::Empty => (0, None),
+            Wrange::Convergent(Bounds(a, b)) => (
+                1,
+                Some((
+                    a.inner().clone(),
+                    inclusive(a),
+                    b.inner().clone(),
+                    inclusive(b),
+                )),
+            ),
+            Wrange::Divergent(Bounds(a, b)) => (
+                2,
+                Some((
+                    a.inner().clone(),
+                    inclusive(a),
+                    b.inner().clone(),
+                    inclusive(b),
+                )),
+            ),
+            Wrange::Full => (3, None),
+        }
+    };
+    key(a).cmp(&key(b))
+}
+
+impl<T> Display for WrangeSet<T>
+where
+    T: Ord + Hash + Clone + Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ranges = self.to_vec();
+        ranges.sort_by(display_order);
+        for (i, r) in ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", r)?;
+        }
+        Ok(())
+    }
+}
+
+/// A cursor over the input being parsed
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error<X>(&self, kind: ParseErrorKind) -> ParseResult<X> {
+        Err(ParseError {
+            position: self.pos,
+            kind,
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consume the given token if it comes next
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn finish(&mut self) -> ParseResult<()> {
+        self.skip_whitespace();
+        if self.is_done() {
+            Ok(())
+        } else {
+            self.error(ParseErrorKind::TrailingInput)
+        }
+    }
+
+    /// Parse a value which runs up to (but not including) any of the terminators
+    fn value<T>(&mut self, terminators: &[char]) -> ParseResult<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(terminators).unwrap_or(rest.len());
+        let text = rest[..len].trim_end();
+        match text.parse() {
+            Ok(x) => {
+                self.pos += len;
+                Ok(x)
+            }
+            Err(_) if text.is_empty() => self.error(ParseErrorKind::Expected("a value")),
+            Err(e) => self.error(ParseErrorKind::InvalidValue(e.to_string())),
+        }
+    }
+
+    fn open(&mut self) -> ParseResult<bool> {
+        if self.eat("[") {
+            Ok(true)
+        } else if self.eat("(") {
+            Ok(false)
+        } else {
+            self.error(ParseErrorKind::Expected("`[` or `(`"))
+        }
+    }
+
+    fn close(&mut self) -> ParseResult<bool> {
+        if self.eat("]") {
+            Ok(true)
+        } else if self.eat(")") {
+            Ok(false)
+        } else {
+            self.error(ParseErrorKind::Expected("`]` or `)`"))
+        }
+    }
+
+    fn bound<T>(&mut self) -> ParseResult<Bound<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.skip_whitespace();
+        let start = self.pos;
+        let inclusive = self.open()?;
+        let x = self.value(&[']', ')'])?;
+        self.skip_whitespace();
+        if self.close()? != inclusive {
+            self.pos = start;
+            return self.error(ParseErrorKind::Expected("matching brackets"));
+        }
+        Ok(if inclusive {
+            Bound::Inclusive(x)
+        } else {
+            Bound::Exclusive(x)
+        })
+    }
+
+    fn wrange<T>(&mut self) -> ParseResult<Wrange<T>>
+    where
+        T: PartialOrd + FromStr,
+        T::Err: Display,
+    {
+        self.skip_whitespace();
+        if self.eat("∅") || self.eat("empty") {
+            return Ok(Wrange::Empty);
+        }
+        if self.eat("full") {
+            return Ok(Wrange::Full);
+        }
+        let start = self.pos;
+        let wrap = self.eat("~");
+        self.skip_whitespace();
+        if !self.rest().starts_with(['[', '(']) {
+            return self.error(ParseErrorKind::Expected("a range"));
+        }
+        let start_inclusive = self.open()?;
+        let a: T = self.value(&[','])?;
+        if !self.eat(",") {
+            return self.error(ParseErrorKind::Expected("`,`"));
+        }
+        let b: T = self.value(&[']', ')'])?;
+        self.skip_whitespace();
+        let end_inclusive = self.close()?;
+        let bound = |inclusive, x| {
+            if inclusive {
+                Bound::Inclusive(x)
+            } else {
+                Bound::Exclusive(x)
+            }
+        };
+        let bounds = Bounds(bound(start_inclusive, a), bound(end_inclusive, b));
+        if wrap {
+            if bounds.0.overlaps(&bounds.1) {
+                Ok(Wrange::Divergent(bounds))
+            } else {
+                self.pos = start;
+                self.error(ParseErrorKind::MisplacedWrap)
+            }
+        } else {
+            Ok(Wrange::new(bounds.0, bounds.1))
+        }
+    }
+}
+
+impl<T> FromStr for Bound<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let b = p.bound()?;
+        p.finish()?;
+        Ok(b)
+    }
+}
+
+impl<T> FromStr for Wrange<T>
+where
+    T: PartialOrd + FromStr,
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let r = p.wrange()?;
+        p.finish()?;
+        Ok(r)
+    }
+}
+
+impl<T> FromStr for WrangeSet<T>
+where
+    T: PartialOrd + Eq + Hash + FromStr,
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let mut ranges = vec![];
+        p.skip_whitespace();
+        if !p.is_done() {
+            loop {
+                ranges.push(p.wrange()?);
+                p.skip_whitespace();
+                if !p.eat(",") {
+                    break;
+                }
+            }
+        }
+        p.finish()?;
+        Ok(ranges.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bound::*;
+    use Wrange::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Inclusive(3).to_string(), "[3]");
+        assert_eq!(Exclusive(-3).to_string(), "(-3)");
+
+        assert_eq!(Wrange::<u8>::Empty.to_string(), "∅");
+        assert_eq!(Wrange::<u8>::Full.to_string(), "full");
+        assert_eq!(
+            Wrange::<u8>::new(Inclusive(10), Exclusive(20)).to_string(),
+            "[10, 20)"
+        );
+        assert_eq!(
+            Wrange::<u8>::new(Exclusive(250), Inclusive(5)).to_string(),
+            "(250, 5]"
+        );
+        assert_eq!(Wrange::<u8>::new_inclusive(7, 7).to_string(), "[7, 7]");
+        assert_eq!(
+            Divergent(Bounds(Exclusive(7u8), Exclusive(7))).to_string(),
+            "~(7, 7)"
+        );
+
+        let set: WrangeSet<u8> = vec![
+            Wrange::new_inclusive(10, 20),
+            Wrange::new_inclusive(250, 5),
+            Wrange::new(Inclusive(1), Exclusive(5)),
+            Empty,
+        ]
+        .into();
+        assert_eq!(set.to_string(), "∅, [1, 5), [10, 20], [250, 5]");
+        assert_eq!(WrangeSet::<u8>::from(vec![]).to_string(), "");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("[3]".parse(), Ok(Inclusive(3u8)));
+        assert_eq!(" ( -3 ) ".parse(), Ok(Exclusive(-3i32)));
+
+        assert_eq!("∅".parse(), Ok(Wrange::<u8>::Empty));
+        assert_eq!(" empty ".parse(), Ok(Wrange::<u8>::Empty));
+        assert_eq!("full".parse(), Ok(Wrange::<u8>::Full));
+        assert_eq!(
+            "[10, 20)".parse(),
+            Ok(Wrange::<u8>::new(Inclusive(10), Exclusive(20)))
+        );
+        assert_eq!(
+            "[10,20)".parse(),
+            Ok(Wrange::<u8>::new(Inclusive(10), Exclusive(20)))
+        );
+        assert_eq!(
+            "(250, 5]".parse(),
+            Ok(Divergent(Bounds(Exclusive(250u8), Inclusive(5))))
+        );
+        assert_eq!(
+            "(5, 5)".parse(),
+            Ok(Convergent(Bounds(Exclusive(5u8), Exclusive(5))))
+        );
+        assert_eq!(
+            "~(5, 5)".parse(),
+            Ok(Divergent(Bounds(Exclusive(5u8), Exclusive(5))))
+        );
+        assert_eq!(
+            "~ [5, 5)".parse(),
+            Ok(Divergent(Bounds(Inclusive(5u8), Exclusive(5))))
+        );
+
+        assert_eq!(
+            "[1, 5), full,∅".parse(),
+            Ok(WrangeSet::<u8>::from(vec![
+                Wrange::new(Inclusive(1), Exclusive(5)),
+                Full,
+                Empty
+            ]))
+        );
+        assert_eq!("  ".parse(), Ok(WrangeSet::<u8>::from(vec![])));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |s: &str| s.parse::<Wrange<u8>>().unwrap_err();
+        let at = |position, kind| ParseError { position, kind };
+        use ParseErrorKind::*;
+
+        assert_eq!(err(""), at(0, Expected("a range")));
+        assert_eq!(
+            err("[1 2]"),
+            at(1, InvalidValue("invalid digit found in string".into()))
+        );
+        assert_eq!(err("[1, 2"), at(5, Expected("`]` or `)`")));
+        assert_eq!(err("[1, ]"), at(4, Expected("a value")));
+        assert_eq!(
+            err("[1, 300]"),
+            at(
+                4,
+                InvalidValue("number too large to fit in target type".into())
+            )
+        );
+        assert_eq!(err("[1, 2] x"), at(7, TrailingInput));
+        assert_eq!(err("~[1, 2]"), at(0, MisplacedWrap));
+        assert_eq!(err("fullness"), at(4, TrailingInput));
+
+        assert_eq!(
+            "[1)".parse::<Bound<u8>>().unwrap_err(),
+            at(0, Expected("matching brackets"))
+        );
+        assert_eq!(
+            "[1, 2], ".parse::<WrangeSet<u8>>().unwrap_err(),
+            at(8, Expected("a range"))
+        );
+        assert_eq!(
+            "[1, 2] [3, 4]".parse::<WrangeSet<u8>>().unwrap_err(),
+            at(7, TrailingInput)
+        );
+    }
+}
@@ -1,11 +1,14 @@
-use crate::{bound::Bounds, Bound, WrangeSet};
-use std::hash::Hash;
+#[cfg(feature = "alloc")]
+use crate::WrangeSet;
+use crate::{bound::Bounds, Bound};
+use arrayvec::ArrayVec;
+use core::hash::Hash;
+#[cfg(feature = "alloc")]
+use core::ops::{BitAnd, BitOr, BitXor, Not, Sub};
+use core::ops::{Range, RangeFull, RangeInclusive};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub enum Wrange<T>
-where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug,
-{
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Wrange<T> {
     Empty,
     Convergent(Bounds<T>),
     Divergent(Bounds<T>),
@@ -14,7 +17,7 @@ where
 
 impl<T> Wrange<T>
 where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug,
+    T: PartialOrd,
 {
     pub fn new(a: Bound<T>, b: Bound<T>) -> Self {
         if a > b {
@@ -40,6 +43,27 @@ where
         Self::new(Bound::Exclusive(a), Bound::Exclusive(b))
     }
 
+    /// The bound at which this range starts, or None for Empty and Full.
+    pub fn start(&self) -> Option<&Bound<T>> {
+        match self {
+            Self::Convergent(Bounds(a, _)) | Self::Divergent(Bounds(a, _)) => Some(a),
+            Self::Empty | Self::Full => None,
+        }
+    }
+
+    /// The bound at which this range ends, or None for Empty and Full.
+    pub fn end(&self) -> Option<&Bound<T>> {
+        match self {
+            Self::Convergent(Bounds(_, b)) | Self::Divergent(Bounds(_, b)) => Some(b),
+            Self::Empty | Self::Full => None,
+        }
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: PartialOrd + Clone,
+{
     /// Perform some sensible normalizations:
     /// - Two overlapping (colocated) endpoints with both inclusive and exclusive
     ///   representation are equivalent to two overlapping inclusive endpoints
@@ -65,73 +89,199 @@ where
             Full => Full,
         }
     }
+}
+
+/// `a..b` is the range [a, b), which is Divergent if `b` comes before `a`.
+/// As with [`Wrange::new`], `a..a` contains `a`.
+impl<T> From<Range<T>> for Wrange<T>
+where
+    T: PartialOrd,
+{
+    fn from(r: Range<T>) -> Self {
+        Self::new(Bound::Inclusive(r.start), Bound::Exclusive(r.end))
+    }
+}
+
+/// `a..=b` is the range [a, b], which is Divergent if `b` comes before `a`
+impl<T> From<RangeInclusive<T>> for Wrange<T>
+where
+    T: PartialOrd,
+{
+    fn from(r: RangeInclusive<T>) -> Self {
+        let (a, b) = r.into_inner();
+        Self::new(Bound::Inclusive(a), Bound::Inclusive(b))
+    }
+}
+
+/// `..` is Full
+impl<T> From<RangeFull> for Wrange<T> {
+    fn from(_: RangeFull) -> Self {
+        Self::Full
+    }
+}
 
+#[cfg(feature = "alloc")]
+impl<T> Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    /// The points in either range, in canonical form
     pub fn union(a: &Self, b: &Self) -> WrangeSet<T> {
-        todo!()
+        WrangeSet::union_all([a.clone(), b.clone()])
     }
 
     pub fn intersection(a: &Self, b: &Self) -> WrangeSet<T> {
+        Self::intersect_pair(a, b).into_iter().collect()
+    }
+}
+
+/// The intersection of two ranges, in canonical form
+#[cfg(feature = "alloc")]
+impl<T> BitAnd for &Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitand(self, rhs: Self) -> WrangeSet<T> {
+        Wrange::intersection(self, rhs).canonicalized()
+    }
+}
+
+/// The union of two ranges, in canonical form
+#[cfg(feature = "alloc")]
+impl<T> BitOr for &Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitor(self, rhs: Self) -> WrangeSet<T> {
+        Wrange::union(self, rhs)
+    }
+}
+
+/// The points in one range but not the other, in canonical form
+#[cfg(feature = "alloc")]
+impl<T> Sub for &Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn sub(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::difference(&self.clone().into(), &rhs.clone().into())
+    }
+}
+
+/// The points in exactly one of two ranges, in canonical form
+#[cfg(feature = "alloc")]
+impl<T> BitXor for &Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitxor(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::symmetric_difference(&self.clone().into(), &rhs.clone().into())
+    }
+}
+
+/// The points not in a range, in canonical form
+#[cfg(feature = "alloc")]
+impl<T> Not for &Wrange<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn not(self) -> WrangeSet<T> {
+        WrangeSet::from(self.clone()).complement()
+    }
+}
+
+impl<T> Wrange<T>
+where
+    T: Ord + Clone,
+{
+    /// The same ranges as [`Wrange::intersection`], without allocating:
+    /// the intersection of two ranges is never more than two ranges.
+    pub fn intersect_pair(a: &Self, b: &Self) -> ArrayVec<Self, 2> {
+        // colocated endpoints behave differently once normalized, e.g. [3, 3)
+        // contains 3, so settle that before comparing any bounds
+        Self::intersect_normalized(&a.clone().normalized(), &b.clone().normalized())
+    }
+
+    fn intersect_normalized(a: &Self, b: &Self) -> ArrayVec<Self, 2> {
         use Wrange::*;
         match (a, b) {
-            (Empty, _) | (_, Empty) => vec![Empty].into(),
+            (Empty, _) | (_, Empty) => pair([Empty]),
 
-            (Full, x) | (x, Full) => vec![x.clone()].into(),
+            (Full, x) | (x, Full) => pair([x.clone()]),
 
             (Convergent(Bounds(a0, a1)), Convergent(Bounds(b0, b1))) => {
                 if a0 > b0 {
                     // flip it so that a0 <= b0 always
-                    Self::intersection(b, a)
+                    Self::intersect_normalized(b, a)
                 } else if a1 < b0 {
                     // covers all disjoint cases
-                    vec![Empty].into()
+                    pair([Empty])
                 } else if a1.overlaps(b0) {
                     // e.g.
                     // |  o----o       |
                     // |       x----o  |
-                    let bound = Bound::intersection_min(&a1, &b0);
-                    vec![Self::new(bound.clone(), bound)].into()
+                    let bound = Bound::intersection_min(a1, b0);
+                    pair([Self::new(bound.clone(), bound)])
                 } else {
                     // all other intersecting cases are covered here
-                    vec![Self::new(
-                        Bound::intersection_max(&a0, &b0),
-                        Bound::intersection_min(&a1, &b1),
-                    )]
-                    .into()
+                    let lo = Bound::intersection_max(a0, b0);
+                    let hi = Bound::intersection_min(a1, b1);
+                    if lo.overlaps(&hi) && lo != hi {
+                        // e.g.
+                        // |  x----o       |
+                        // |  o            |
+                        // colocated endpoints of mixed inclusivity would
+                        // normalize to a single point, but here the ranges only touch
+                        pair([Empty])
+                    } else {
+                        pair([Self::new(lo, hi)])
+                    }
                 }
             }
 
             (Divergent(Bounds(a0, a1)), Divergent(Bounds(b0, b1))) => {
                 if a0 > b0 {
                     // flip it so that a0 <= b0 always
-                    Self::intersection(b, a)
+                    Self::intersect_normalized(b, a)
                 } else if a0 <= b1 {
                     // e.g.
                     // |----o    o------|
                     // |-----------o o--|
-                    vec![
+                    pair([
                         Self::new(
                             Bound::intersection_min(a0, b1),
                             Bound::intersection_max(a0, b1),
                         ),
-                        Self::new(
+                        // be explicit, since b0 and a1 may be colocated
+                        Divergent(Bounds(
                             Bound::intersection_max(b0, a1),
                             Bound::intersection_min(b0, a1),
-                        ),
-                    ]
-                    .into()
+                        )),
+                    ])
                 } else {
                     // e.g.
                     // |-----o   o------|
                     // |---o        o---|
-                    vec![Self::new(
-                        Bound::intersection_max(&a0, &b0),
-                        Bound::intersection_min(&a1, &b1),
-                    )]
-                    .into()
+                    // be explicit, since the later start and the earlier end
+                    // may be colocated, which Self::new would make Convergent
+                    pair([Divergent(Bounds(
+                        Bound::intersection_max(a0, b0),
+                        Bound::intersection_min(a1, b1),
+                    ))])
                 }
             }
 
-            (Convergent(Bounds(_, _)), Divergent(Bounds(_, _))) => Self::intersection(b, a),
+            (Convergent(Bounds(_, _)), Divergent(Bounds(_, _))) => Self::intersect_normalized(b, a),
 
             (Divergent(Bounds(a0, a1)), Convergent(Bounds(b0, b1))) => {
                 // four possible cases:
@@ -148,16 +298,16 @@ where
                 //   |----o      o----|
                 //   | o------------o |
                 match (a1 >= b0, a0 <= b1) {
-                    (false, false) => vec![Empty],
-                    (true, false) => vec![Self::new(
+                    (false, false) => pair([Empty]),
+                    (true, false) => pair([Self::new(
                         Bound::intersection_min(a1, b0),
                         Bound::intersection_min(a1, b1),
-                    )],
-                    (false, true) => vec![Self::new(
+                    )]),
+                    (false, true) => pair([Self::new(
                         Bound::intersection_max(a0, b0),
                         Bound::intersection_max(a0, b1),
-                    )],
-                    (true, true) => vec![
+                    )]),
+                    (true, true) => pair([
                         Self::new(
                             Bound::intersection_min(a1, b0),
                             Bound::intersection_max(a1, b0),
@@ -166,14 +316,18 @@ where
                             Bound::intersection_min(a0, b1),
                             Bound::intersection_max(a0, b1),
                         ),
-                    ],
+                    ]),
                 }
-                .into()
             }
         }
     }
 }
 
+/// Collect at most two ranges
+fn pair<T, const N: usize>(ranges: [Wrange<T>; N]) -> ArrayVec<Wrange<T>, 2> {
+    IntoIterator::into_iter(ranges).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,4 +357,43 @@ mod tests {
             Full,
         );
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_intersect_pair() {
+        use Bound::*;
+        use Wrange::*;
+
+        let a = Wrange::<u32>::new_inclusive(100, 10);
+        let b = Wrange::<u32>::new_inclusive(5, 200);
+        let pair = Wrange::intersect_pair(&a, &b);
+        assert_eq!(
+            pair.as_slice(),
+            &[
+                Convergent(Bounds(Inclusive(5), Inclusive(10))),
+                Convergent(Bounds(Inclusive(100), Inclusive(200))),
+            ]
+        );
+        assert_eq!(WrangeSet::from(pair.to_vec()), Wrange::intersection(&a, &b));
+        assert_eq!(Wrange::intersect_pair(&a, &Empty).as_slice(), &[Empty]);
+        assert_eq!(Wrange::intersect_pair(&Full, &a).as_slice(), &[a]);
+    }
+
+    #[test]
+    fn test_minimal_bounds() {
+        use crate::Relation;
+        use Bound::*;
+
+        // neither Hash nor Debug
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+        struct Plain(u8);
+
+        let a = Wrange::new(Inclusive(Plain(10)), Exclusive(Plain(2)));
+        let b = Wrange::new_inclusive(Plain(0), Plain(1));
+        assert!(matches!(a, Wrange::Divergent(_)));
+        assert!(a.contains(&Plain(1)));
+        assert!(a.is_superset_of(&b));
+        assert!(a.relation(&b) == Relation::Contains);
+        assert!(a.clone().normalized() == a);
+    }
 }
@@ -1,16 +1,68 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Not, Sub};
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+#[cfg(feature = "std")]
 use std::collections::HashSet;
-use std::hash::Hash;
 
-use crate::Wrange;
+use crate::segment::{cmp_hi, cmp_lo, Segment};
+use crate::{Bound, Bounds, Wrange};
 
-#[derive(PartialEq, Eq, Debug, derive_more::From, derive_more::IntoIterator)]
-pub struct WrangeSet<T>(HashSet<Wrange<T>>)
+#[derive(Clone, Debug, derive_more::From)]
+pub struct WrangeSet<T>(HashSet<Wrange<T>>);
+
+/// A set with no members
+impl<T> Default for WrangeSet<T> {
+    fn default() -> Self {
+        Self(HashSet::new())
+    }
+}
+
+impl<T> PartialEq for WrangeSet<T>
+where
+    T: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for WrangeSet<T> where T: Eq + Hash {}
+
+/// The canonical ranges are hashed in order, so that the hash does not depend
+/// on the order in which members are stored. Sets with the same members
+/// always have the same canonical ranges.
+impl<T> Hash for WrangeSet<T>
 where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug;
+    T: Ord + Hash + Clone,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_canonical_vec().hash(state);
+    }
+}
+
+impl<T> WrangeSet<T> {
+    /// The ranges of this set, as stored, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &Wrange<T>> {
+        self.0.iter()
+    }
+
+    /// The number of ranges in this set, as stored. This counts any Empty
+    /// or overlapping members, unlike the length of
+    /// [`WrangeSet::to_canonical_vec`].
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
 
 impl<T> WrangeSet<T>
 where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug,
+    T: Ord + Hash + Clone,
 {
     pub fn normalized(self) -> Self {
         Self(self.0.into_iter().map(|r| r.normalized()).collect())
@@ -20,6 +72,139 @@ where
         self.0.clone().into_iter().collect()
     }
 
+    /// The ranges of this set in canonical form: normalized, disjoint,
+    /// with no two ranges touching, and ordered by their start bounds, so that
+    /// two sets covering the same points always produce the same ranges.
+    /// A Divergent range, if any, comes last. An empty set produces no ranges.
+    pub fn to_canonical_vec(&self) -> Vec<Wrange<T>> {
+        let mut segments: Vec<Segment<T>> = self.0.iter().flat_map(|r| r.segments()).collect();
+        segments.sort_by(|a, b| cmp_lo(a.lo, b.lo));
+
+        let mut merged: Vec<Segment<T>> = Vec::with_capacity(segments.len());
+        for s in segments {
+            match merged.last_mut() {
+                Some(last) if last.reaches(&s) => {
+                    if cmp_hi(s.hi, last.hi) == Ordering::Greater {
+                        last.hi = s.hi;
+                    }
+                }
+                _ => merged.push(s),
+            }
+        }
+
+        join(
+            merged
+                .into_iter()
+                .map(|s| (s.lo.cloned(), s.hi.cloned()))
+                .collect(),
+        )
+    }
+
+    /// Rewrite this set in canonical form. See [`WrangeSet::to_canonical_vec`].
+    pub fn canonicalized(self) -> Self {
+        Self(self.to_canonical_vec().into_iter().collect())
+    }
+
+    /// The points covered by any of these sets, in canonical form.
+    ///
+    /// This sorts all of the ranges once and sweeps over them, so it takes
+    /// O(n log n) time for n ranges in total.
+    pub fn union_all<I, S>(sets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Self>,
+    {
+        Self(sets.into_iter().flat_map(|s| s.into().0).collect()).canonicalized()
+    }
+
+    /// The points covered by every one of these sets, in canonical form.
+    /// With no sets at all, this is Full.
+    ///
+    /// Like [`WrangeSet::union_all`], this is a single sweep over the sorted
+    /// endpoints of all of the ranges, taking O(n log n) time for n ranges in
+    /// total.
+    pub fn intersection_all<I, S>(sets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Self>,
+    {
+        let sets: Vec<Vec<Wrange<T>>> = sets
+            .into_iter()
+            .map(|s| s.into().to_canonical_vec())
+            .collect();
+        let n = sets.len();
+        Self::sweep(&sets, |depth| depth == n)
+    }
+
+    /// The points in `a` but not in `b`, in canonical form
+    pub fn difference(a: &Self, b: &Self) -> Self {
+        Self::sweep(&[a.to_canonical_vec(), b.complement().to_vec()], |depth| {
+            depth == 2
+        })
+    }
+
+    /// The points in exactly one of `a` and `b`, in canonical form
+    pub fn symmetric_difference(a: &Self, b: &Self) -> Self {
+        Self::sweep(&[a.to_canonical_vec(), b.to_canonical_vec()], |depth| {
+            depth == 1
+        })
+    }
+
+    /// The points not in this set, in canonical form
+    pub fn complement(&self) -> Self {
+        Self::sweep(&[self.to_canonical_vec()], |depth| depth == 0)
+    }
+
+    /// Sweep up over the sorted endpoints of these sets, each in canonical
+    /// form, keeping the points covered by a number of sets for which `keep`
+    /// is true. Within a canonical set no two ranges overlap, so the number
+    /// of sets covering a point is the number of segments covering it.
+    fn sweep(sets: &[Vec<Wrange<T>>], keep: impl Fn(usize) -> bool) -> Self {
+        let mut depth: usize = 0;
+        let mut edges: Vec<(Edge<T>, isize)> = vec![];
+        for s in sets.iter().flatten().flat_map(|r| r.segments()) {
+            match s.lo {
+                Some(lo) => edges.push((Edge::start(lo), 1)),
+                None => depth += 1,
+            }
+            if let Some(hi) = s.hi {
+                edges.push((Edge::end(hi), -1));
+            }
+        }
+        edges.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        let mut segments = vec![];
+        // the start of the segment being swept over, if inside one
+        let mut lo = if keep(depth) { Some(None) } else { None };
+        // edges at the same point change the depth together
+        let mut rest = &edges[..];
+        while let Some((edge, _)) = rest.first() {
+            let n = rest.iter().take_while(|(e, _)| e == edge).count();
+            let (group, tail) = rest.split_at(n);
+            rest = tail;
+            let change: isize = group.iter().map(|(_, d)| d).sum();
+            depth = usize::try_from(depth as isize + change)
+                .expect("every segment ends after it starts");
+            match (lo.take(), keep(depth)) {
+                (None, true) => lo = Some(Some(edge.lo())),
+                (Some(start), false) => segments.push((start, Some(edge.hi()))),
+                (start, _) => lo = start,
+            }
+        }
+        segments.extend(lo.map(|start| (start, None)));
+        Self(join(segments).into_iter().collect())
+    }
+
+    /// Whether this set contains no points at all, whatever its members
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|r| r.segments().next().is_none())
+    }
+
+    /// Whether this set contains every point
+    pub fn is_full(&self) -> bool {
+        self.to_canonical_vec() == [Wrange::Full]
+    }
+
     pub fn union(a: &Self, b: &Self) -> Self {
         Self(a.0.iter().chain(b.0.iter()).cloned().collect())
     }
@@ -36,9 +221,85 @@ where
     }
 }
 
+/// A point at which coverage can change during a sweep: either at a value,
+/// or just above it, with nothing in between.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Edge<'a, T> {
+    at: &'a T,
+    above: bool,
+}
+
+impl<'a, T: Clone> Edge<'a, T> {
+    /// Where a segment with this lower bound starts
+    fn start(lo: &'a Bound<T>) -> Self {
+        match lo {
+            Bound::Inclusive(at) => Self { at, above: false },
+            Bound::Exclusive(at) => Self { at, above: true },
+        }
+    }
+
+    /// Where a segment with this upper bound has just ended
+    fn end(hi: &'a Bound<T>) -> Self {
+        match hi {
+            Bound::Inclusive(at) => Self { at, above: true },
+            Bound::Exclusive(at) => Self { at, above: false },
+        }
+    }
+
+    /// The lower bound of a segment starting here
+    fn lo(&self) -> Bound<T> {
+        if self.above {
+            Bound::Exclusive(self.at.clone())
+        } else {
+            Bound::Inclusive(self.at.clone())
+        }
+    }
+
+    /// The upper bound of a segment which has just ended here
+    fn hi(&self) -> Bound<T> {
+        if self.above {
+            Bound::Inclusive(self.at.clone())
+        } else {
+            Bound::Exclusive(self.at.clone())
+        }
+    }
+}
+
+/// The lower and upper bounds of a segment, where `None` stands for an
+/// unbounded end
+type Ends<T> = (Option<Bound<T>>, Option<Bound<T>>);
+
+/// Turn disjoint segments, ordered by their starts, into ranges. The
+/// segments unbounded below and above, if any, are joined into one Divergent
+/// range which comes last.
+fn join<T>(mut segments: Vec<Ends<T>>) -> Vec<Wrange<T>> {
+    // A segment unbounded below only comes from a Divergent range,
+    // so there must also be a segment unbounded above to join it with
+    let wraps = segments.first().map_or(false, |s| s.0.is_none()) && segments.len() > 1;
+    let divergent = if wraps {
+        let (first, last) = (segments.remove(0), segments.pop());
+        last.map(|last| match (last.0, first.1) {
+            (Some(a), Some(b)) => Wrange::Divergent(Bounds(a, b)),
+            _ => unreachable!("only the outermost segments can be unbounded"),
+        })
+    } else {
+        None
+    };
+
+    segments
+        .into_iter()
+        .map(|s| match s {
+            (Some(a), Some(b)) => Wrange::Convergent(Bounds(a, b)),
+            (None, None) => Wrange::Full,
+            _ => unreachable!("only the outermost segments can be unbounded"),
+        })
+        .chain(divergent)
+        .collect()
+}
+
 impl<T> From<Vec<Wrange<T>>> for WrangeSet<T>
 where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug,
+    T: Eq + Hash,
 {
     fn from(v: Vec<Wrange<T>>) -> Self {
         Self(v.into_iter().collect())
@@ -47,9 +308,355 @@ where
 
 impl<T> From<Wrange<T>> for WrangeSet<T>
 where
-    T: PartialOrd + Ord + Hash + Clone + std::fmt::Debug,
+    T: Eq + Hash,
 {
     fn from(r: Wrange<T>) -> Self {
         vec![r].into()
     }
 }
+
+impl<T> FromIterator<Wrange<T>> for WrangeSet<T>
+where
+    T: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = Wrange<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for WrangeSet<T> {
+    type Item = Wrange<T>;
+    type IntoIter = <HashSet<Wrange<T>> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a WrangeSet<T> {
+    type Item = &'a Wrange<T>;
+    type IntoIter = <&'a HashSet<Wrange<T>> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T> Extend<Wrange<T>> for WrangeSet<T>
+where
+    T: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = Wrange<T>>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+/// The union of two sets, in canonical form
+impl<T> BitOr for &WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitor(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::union(self, rhs).canonicalized()
+    }
+}
+
+/// The intersection of two sets, in canonical form
+impl<T> BitAnd for &WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitand(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::sweep(
+            &[self.to_canonical_vec(), rhs.to_canonical_vec()],
+            |depth| depth == 2,
+        )
+    }
+}
+
+/// The difference of two sets, in canonical form
+impl<T> Sub for &WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn sub(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::difference(self, rhs)
+    }
+}
+
+/// The symmetric difference of two sets, in canonical form
+impl<T> BitXor for &WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn bitxor(self, rhs: Self) -> WrangeSet<T> {
+        WrangeSet::symmetric_difference(self, rhs)
+    }
+}
+
+/// The complement of a set, in canonical form
+impl<T> Not for &WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = WrangeSet<T>;
+
+    fn not(self) -> WrangeSet<T> {
+        self.complement()
+    }
+}
+
+/// The same operators on owned sets, which borrow them
+macro_rules! forward_owned_ops {
+    ($($op:ident::$f:ident),*) => {
+        $(
+            impl<T> $op for WrangeSet<T>
+            where
+                T: Ord + Hash + Clone,
+            {
+                type Output = Self;
+
+                fn $f(self, rhs: Self) -> Self {
+                    $op::$f(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_ops!(BitOr::bitor, BitAnd::bitand, Sub::sub, BitXor::bitxor);
+
+impl<T> Not for WrangeSet<T>
+where
+    T: Ord + Hash + Clone,
+{
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::ascii;
+    use crate::Bound::*;
+    use Wrange::*;
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(
+            ascii("  o--o  o--o    ").to_canonical_vec(),
+            vec![Wrange::new_inclusive(2, 5), Wrange::new_inclusive(8, 11)]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("  o--o          "), &ascii("    o----o      "))
+                .to_canonical_vec(),
+            vec![Wrange::new_inclusive(2, 9)]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("  o--x          "), &ascii("     o---o      "))
+                .to_canonical_vec(),
+            vec![Wrange::new_inclusive(2, 9)]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("  o--x          "), &ascii("     x---o      "))
+                .to_canonical_vec(),
+            vec![
+                Wrange::new(Inclusive(2), Exclusive(5)),
+                Wrange::new(Exclusive(5), Inclusive(9))
+            ]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("--o          o--"), &ascii("    o-------o   "))
+                .to_canonical_vec(),
+            vec![
+                Wrange::new_inclusive(4, 12),
+                Divergent(Bounds(Inclusive(13), Inclusive(2)))
+            ]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("--o          o--"), &ascii("  o----------o  "))
+                .to_canonical_vec(),
+            vec![Full]
+        );
+        assert_eq!(
+            WrangeSet::union(&ascii("--o          o--"), &ascii("o--o     o----o "))
+                .to_canonical_vec(),
+            vec![Divergent(Bounds(Inclusive(9), Inclusive(3)))]
+        );
+        assert_eq!(ascii("                ").to_canonical_vec(), vec![]);
+        assert_eq!(ascii("----------------").to_canonical_vec(), vec![Full]);
+        assert_eq!(
+            ascii("-----x----------").to_canonical_vec(),
+            vec![Divergent(Bounds(Exclusive(5), Exclusive(5)))]
+        );
+        assert_eq!(
+            WrangeSet::from(vec![
+                Empty,
+                Wrange::new_exclusive(3, 3),
+                Wrange::new(Inclusive(4), Exclusive(4))
+            ])
+            .to_canonical_vec(),
+            vec![Wrange::new_inclusive(4, 4)]
+        );
+    }
+    #[test]
+    fn test_union_all() {
+        assert_eq!(
+            WrangeSet::union_all(vec![
+                ascii("  o--o          "),
+                ascii("    o----o      "),
+                ascii("            o-x "),
+            ]),
+            ascii("  o------o  o-x ").canonicalized()
+        );
+        assert_eq!(
+            WrangeSet::union_all(vec![Wrange::new_exclusive(5u8, 9), Full]),
+            Full.into()
+        );
+        assert_eq!(
+            WrangeSet::<u8>::union_all(Vec::<Wrange<u8>>::new()),
+            WrangeSet::from(vec![])
+        );
+    }
+
+    #[test]
+    fn test_intersection_all() {
+        assert_eq!(
+            WrangeSet::intersection_all(vec![
+                ascii("--o   o--o   o--"),
+                ascii("-----o      o---"),
+                ascii("---x      x-----"),
+            ]),
+            ascii("--o          o--").canonicalized()
+        );
+        assert_eq!(
+            WrangeSet::intersection_all(
+                vec![ascii("  o--x          "), ascii("     x---o      "),]
+            ),
+            WrangeSet::from(vec![])
+        );
+        assert_eq!(
+            WrangeSet::intersection_all(
+                vec![ascii("  o--o          "), ascii("     o---o      "),]
+            ),
+            Wrange::new_inclusive(5, 5).into()
+        );
+        assert_eq!(
+            WrangeSet::intersection_all(vec![ascii("----------------"), ascii("-----x----------")]),
+            Divergent(Bounds(Exclusive(5), Exclusive(5))).into()
+        );
+        assert_eq!(
+            WrangeSet::<u8>::intersection_all(Vec::<Wrange<u8>>::new()),
+            Full.into()
+        );
+    }
+
+    #[test]
+    fn test_collect() {
+        let mut set: WrangeSet<u8> = (1..4).map(|x| Wrange::new_inclusive(x, x)).collect();
+        set.extend(vec![Empty, Full]);
+        assert_eq!(set.to_vec().len(), 5);
+        assert_eq!(set.canonicalized(), Full.into());
+    }
+    #[test]
+    fn test_collection() {
+        let empty = WrangeSet::<u8>::default();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert!(!empty.is_full());
+
+        let set = ascii("  o--o     x x  ");
+        assert_eq!(set.len(), 3);
+        assert!(!set.is_empty());
+        assert!(ascii("                ").is_empty());
+        assert!(ascii("----------------").is_full());
+        let halves = WrangeSet::from(Wrange::new_inclusive(0u8, 9))
+            | WrangeSet::from(Wrange::new_exclusive(9u8, 0));
+        assert!(halves.is_full());
+
+        let mut members: Vec<_> = set.iter().cloned().collect();
+        members.sort_by_key(|r| r.start().map(|b| *b.inner()));
+        assert_eq!(members[0], Wrange::new_inclusive(2, 5));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_hash_set() {
+        use std::collections::HashSet;
+        let members: HashSet<_> = vec![Wrange::new_inclusive(2u8, 5), Wrange::Full]
+            .into_iter()
+            .collect();
+        let set = WrangeSet::from(members.clone());
+        assert_eq!((&set).into_iter().count(), 2);
+        let back: HashSet<_> = set.into_iter().collect();
+        assert_eq!(back, members);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |s: &WrangeSet<u8>| {
+            let mut h = DefaultHasher::new();
+            s.hash(&mut h);
+            h.finish()
+        };
+        let a: WrangeSet<u8> = (0..20).map(|x| Wrange::new_inclusive(x, x + 1)).collect();
+        let b: WrangeSet<u8> = (0..20)
+            .rev()
+            .map(|x| Wrange::new_inclusive(x, x + 1))
+            .collect();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&ascii("  o--o     x x  ")));
+
+        // the caller's hasher is used, keys and all
+        use std::collections::hash_map::RandomState;
+        use std::hash::BuildHasher;
+        let hash_with = |k: &RandomState| {
+            let mut h = k.build_hasher();
+            a.hash(&mut h);
+            h.finish()
+        };
+        assert_ne!(
+            hash_with(&RandomState::new()),
+            hash_with(&RandomState::new())
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = ascii("  o-----o       ");
+        let b = ascii("     o-----o    ");
+        assert_eq!(
+            a.clone() | b.clone(),
+            ascii("  o--------o    ").canonicalized()
+        );
+        assert_eq!(
+            a.clone() & b.clone(),
+            ascii("     o--o       ").canonicalized()
+        );
+        assert_eq!(
+            a.clone() - b.clone(),
+            ascii("  o--x          ").canonicalized()
+        );
+        assert_eq!(
+            a.clone() ^ b.clone(),
+            ascii("  o--x  x--o    ").canonicalized()
+        );
+        assert_eq!(!a.clone(), ascii("--x     x-------").canonicalized());
+        assert_eq!(!!a.clone(), a.clone().canonicalized());
+        assert!((a.clone() ^ a.clone()).is_empty());
+        assert!((a.clone() | !a).is_full());
+    }
+}
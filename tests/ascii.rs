@@ -0,0 +1,15 @@
+use proptest::prelude::*;
+use wrange::ascii::{diagram, try_ascii};
+use wrange::WrangeSet;
+
+mod common;
+
+proptest! {
+    /// Every well-formed range has a diagram of its own in the extended
+    /// notation, which reads back as exactly that range
+    #[test]
+    fn diagram_roundtrip(w in common::wrange::<u8>()) {
+        let art = diagram(&w, 256);
+        prop_assert_eq!(try_ascii(&art), Ok(WrangeSet::from(w)), "|{}|", art);
+    }
+}
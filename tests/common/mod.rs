@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+use proptest::prelude::*;
+use wrange::{Bound, Bounds, Wrange, WrappingInteger};
+
+pub fn bound<T: Arbitrary + Clone>() -> impl Strategy<Value = Bound<T>> {
+    prop_oneof![
+        any::<T>().prop_map(Bound::Inclusive),
+        any::<T>().prop_map(Bound::Exclusive),
+    ]
+}
+
+/// Any well-formed Wrange, including the Divergent ranges with colocated
+/// endpoints which `Wrange::new` cannot produce
+pub fn wrange<T: Arbitrary + WrappingInteger>() -> impl Strategy<Value = Wrange<T>> {
+    prop_oneof![
+        Just(Wrange::Empty),
+        Just(Wrange::Full),
+        (bound(), bound()).prop_map(|(a, b)| Wrange::new(a, b)),
+        bound().prop_map(|a: Bound<T>| Wrange::Divergent(Bounds(a, a))),
+    ]
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::wrange;
+use proptest::prelude::*;
+use wrange::{Wrange, WrangeSet};
+
+/// The values of a range, compared by fingerprint
+fn values<T: wrange::WrappingInteger>(w: Wrange<T>) -> u64 {
+    WrangeSet::from(w).fingerprint()
+}
+
+proptest! {
+    #[test]
+    fn wrange_round_trips_u8(w in wrange::<u8>()) {
+        let decoded: Wrange<u8> = Wrange::from_bytes(&w.to_bytes()).unwrap();
+        prop_assert_eq!(values(decoded), values(w));
+    }
+
+    #[test]
+    fn wrange_round_trips_i32(w in wrange::<i32>()) {
+        let decoded: Wrange<i32> = Wrange::from_bytes(&w.to_bytes()).unwrap();
+        prop_assert_eq!(values(decoded), values(w));
+    }
+
+    #[test]
+    fn wrange_round_trips_u64(w in wrange::<u64>()) {
+        let decoded: Wrange<u64> = Wrange::from_bytes(&w.to_bytes()).unwrap();
+        prop_assert_eq!(values(decoded), values(w));
+    }
+
+    #[test]
+    fn wrange_set_round_trips_u16(ws in prop::collection::vec(wrange::<u16>(), 0..10)) {
+        let set: WrangeSet<u16> = ws.into();
+        let decoded: WrangeSet<u16> = WrangeSet::from_bytes(&set.to_bytes()).unwrap();
+        prop_assert_eq!(decoded.fingerprint(), set.fingerprint());
+    }
+
+    #[test]
+    fn wrange_set_round_trips_through_io(ws in prop::collection::vec(wrange::<i64>(), 0..10)) {
+        let set: WrangeSet<i64> = ws.into();
+        let mut buf = vec![];
+        set.write_to(&mut buf).unwrap();
+        let decoded: WrangeSet<i64> = WrangeSet::read_from(&mut &buf[..]).unwrap();
+        prop_assert_eq!(decoded.fingerprint(), set.fingerprint());
+    }
+
+    #[test]
+    fn sets_of_the_same_values_encode_alike(ws in prop::collection::vec(wrange::<u8>(), 0..10)) {
+        let set: WrangeSet<u8> = ws.into();
+        let points: WrangeSet<u8> = (0..=255u8)
+            .filter(|x| set.iter().any(|r| r.contains(x)))
+            .map(|x| Wrange::new_inclusive(x, x))
+            .collect();
+        prop_assert_eq!(points.to_bytes(), set.to_bytes());
+    }
+
+    #[test]
+    fn decoding_accepts_only_canonical_encodings(bytes in prop::collection::vec(any::<u8>(), 0..16)) {
+        if let Ok(w) = Wrange::<u8>::from_bytes(&bytes) {
+            prop_assert_eq!(w.to_bytes(), bytes.clone());
+        }
+        if let Ok(s) = WrangeSet::<u8>::from_bytes(&bytes) {
+            prop_assert_eq!(s.to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn decoding_set_shaped_input_accepts_only_canonical_encodings(
+        count in 0u8..4,
+        records in prop::collection::vec((0u8..16, any::<u8>(), any::<u8>()), 0..4),
+    ) {
+        let mut bytes = vec![wrange::encoding::VERSION, count];
+        for (tag, gap, length) in records {
+            bytes.extend([tag, gap & 0x7f, length & 0x7f]);
+        }
+        if let Ok(s) = WrangeSet::<u8>::from_bytes(&bytes) {
+            prop_assert_eq!(s.to_bytes(), bytes);
+        }
+    }
+}
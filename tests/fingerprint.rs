@@ -0,0 +1,36 @@
+mod common;
+use common::wrange;
+
+use proptest::prelude::*;
+use wrange::WrangeSet;
+
+proptest! {
+    #[test]
+    fn fingerprint_depends_only_on_coverage(ws in prop::collection::vec(wrange::<u8>(), 0..6)) {
+        let set = WrangeSet::from(ws.clone());
+        let canonical = WrangeSet::from(set.to_canonical_vec());
+        prop_assert_eq!(set.fingerprint(), canonical.fingerprint());
+
+        // splitting members up does not change what they cover
+        let split = WrangeSet::from(ws.iter().flat_map(|w| w.split_into(3)).collect::<Vec<_>>());
+        prop_assert_eq!(set.fingerprint(), split.fingerprint());
+    }
+}
+
+fn values(set: &WrangeSet<u8>) -> Vec<u8> {
+    let ranges = set.to_vec();
+    (0..=255)
+        .filter(|x| ranges.iter().any(|r| r.contains(x)))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn fingerprint_distinguishes_values(
+        a in prop::collection::vec(wrange::<u8>(), 0..4),
+        b in prop::collection::vec(wrange::<u8>(), 0..4),
+    ) {
+        let (a, b) = (WrangeSet::from(a), WrangeSet::from(b));
+        prop_assert_eq!(values(&a) == values(&b), a.fingerprint() == b.fingerprint());
+    }
+}
@@ -240,4 +240,92 @@ fn test_intersection_with_overlapping_endpoints() {
     assert_intersection!(ascii("-x-"), ascii(" o "), ascii("   "),);
     assert_intersection!(ascii("-o-"), ascii(" o "), ascii(" o "),);
     assert_intersection!(ascii("-o-"), ascii(" x "), ascii("   "),);
+    assert_intersection!(ascii("-o-"), ascii("-x-"), ascii("-x-"),);
+}
+
+#[test]
+fn test_intersection_with_colocated_ends() {
+    use wrange::{Bound::*, Bounds, Wrange::*};
+
+    // ranges which only touch at a value one of them excludes share nothing
+    assert_intersection!(
+        ascii("  o---x       "),
+        ascii("      o---o   "),
+        ascii("              "),
+    );
+    assert_intersection!(
+        ascii("  o---o       "),
+        ascii("      x---o   "),
+        ascii("              "),
+    );
+    assert_intersection!(
+        ascii("---x     o----"),
+        ascii("   o-----x    "),
+        ascii("              "),
+    );
+
+    // a Divergent range whose ends meet leaves out just the one value, and
+    // intersecting it with itself keeps it whole, beside the empty overlap
+    // of its two ends
+    let all_but_1 = Divergent(Bounds(Exclusive(1), Exclusive(1)));
+    assert_intersection!(
+        ascii("-x-"),
+        ascii("-x-"),
+        vec![Empty, all_but_1].into_iter().collect(),
+    );
+}
+
+#[test]
+fn test_intersection_with_colocated_mixed_inclusivity() {
+    use wrange::{Bound::*, Bounds, Wrange::*};
+
+    // colocated ends of mixed inclusivity normalize to the single point, so
+    // [3, 3) and (3, 3] both hold 3, while (3, 3) holds nothing
+    assert_intersection!(
+        Convergent(Bounds(Inclusive(3), Exclusive(3))).into(),
+        Convergent(Bounds(Exclusive(3), Inclusive(3))).into(),
+        Wrange::new_inclusive(3, 3).into(),
+    );
+    assert_intersection!(
+        Convergent(Bounds(Inclusive(3), Exclusive(3))).into(),
+        Convergent(Bounds(Inclusive(3), Inclusive(3))).into(),
+        Wrange::new_inclusive(3, 3).into(),
+    );
+    assert_intersection!(
+        Convergent(Bounds(Inclusive(3), Exclusive(3))).into(),
+        Convergent(Bounds(Exclusive(3), Exclusive(3))).into(),
+        Empty.into(),
+    );
+
+    // every value but 5, against ranges around and at 5
+    let all_but_5 = Divergent(Bounds(Exclusive(5), Exclusive(5)));
+    assert_intersection!(
+        all_but_5.into(),
+        Wrange::new_inclusive(2, 8).into(),
+        vec![
+            Convergent(Bounds(Inclusive(2), Exclusive(5))),
+            Convergent(Bounds(Exclusive(5), Inclusive(8))),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    assert_intersection!(
+        all_but_5.into(),
+        Wrange::new_inclusive(5, 5).into(),
+        Empty.into(),
+    );
+    assert_intersection!(
+        all_but_5.into(),
+        Convergent(Bounds(Exclusive(5), Inclusive(8))).into(),
+        vec![Empty, Convergent(Bounds(Exclusive(5), Inclusive(8)))]
+            .into_iter()
+            .collect(),
+    );
+
+    // a colocated Divergent range including its end is every value
+    assert_intersection!(
+        Divergent(Bounds(Inclusive(5), Exclusive(5))).into(),
+        Wrange::new_inclusive(2, 8).into(),
+        Wrange::new_inclusive(2, 8).into(),
+    );
 }
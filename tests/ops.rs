@@ -0,0 +1,167 @@
+use wrange::ascii::ascii;
+use wrange::Wrange;
+
+/// The single range drawn by some ASCII art
+fn one(s: &str) -> Wrange<u8> {
+    let ranges = ascii(s).to_vec();
+    assert_eq!(ranges.len(), 1, "not a single range: |{}|", s);
+    ranges[0]
+}
+
+/// Check an operator on borrowed ranges, borrowed sets and owned sets
+macro_rules! assert_op {
+    ($a: expr, $op: tt, $b: expr, $e: expr $(,)?) => {
+        let expected = ascii($e).canonicalized();
+        assert_eq!(&one($a) $op &one($b), expected);
+        assert_eq!(&ascii($a) $op &ascii($b), expected);
+        assert_eq!(ascii($a) $op ascii($b), expected);
+    };
+}
+
+macro_rules! assert_not {
+    ($a: expr, $e: expr $(,)?) => {
+        let expected = ascii($e).canonicalized();
+        assert_eq!(!&one($a), expected);
+        assert_eq!(!&ascii($a), expected);
+        assert_eq!(!ascii($a), expected);
+    };
+}
+
+#[test]
+fn test_bitand() {
+    assert_op!(
+        "  o-----o       ",
+        &,
+        "     o----o     ",
+        "     o--o       ",
+    );
+    assert_op!(
+        "---o        o---",
+        &,
+        "-x       o------",
+        "-x          o---",
+    );
+    assert_op!(
+        "----o    o------",
+        &,
+        "-----------o o--",
+        "----o    o-o o--",
+    );
+    assert_op!(
+        "----------------",
+        &,
+        "  o-----o       ",
+        "  o-----o       ",
+    );
+    assert_op!(
+        "                ",
+        &,
+        "  o-----o       ",
+        "                ",
+    );
+}
+
+#[test]
+fn test_bitor() {
+    assert_op!(
+        "  o-----o       ",
+        |,
+        "     o----o     ",
+        "  o-------o     ",
+    );
+    assert_op!(
+        "  o--o          ",
+        |,
+        "        o--o    ",
+        "  o--o  o--o    ",
+    );
+    assert_op!(
+        "  o--x          ",
+        |,
+        "     o--o       ",
+        "  o-----o       ",
+    );
+    assert_op!(
+        "  o--x          ",
+        |,
+        "     x--o       ",
+        "  o--x--o       ",
+    );
+    assert_op!(
+        "---o        o---",
+        |,
+        "  o------o      ",
+        "---------o  o---",
+    );
+    assert_op!(
+        "--o          o--",
+        |,
+        "  o----------o  ",
+        "----------------",
+    );
+}
+
+#[test]
+fn test_sub() {
+    assert_op!(
+        "  o-----o       ",
+        -,
+        "     o----o     ",
+        "  o--x          ",
+    );
+    assert_op!(
+        "  o---------o   ",
+        -,
+        "     o--o       ",
+        "  o--x  x---o   ",
+    );
+    assert_op!(
+        "---o        o---",
+        -,
+        "  o----------o  ",
+        "--x          x--",
+    );
+    assert_op!(
+        "  o-----o       ",
+        -,
+        "----------------",
+        "                ",
+    );
+    assert_op!(
+        "----------------",
+        -,
+        "  o-----o       ",
+        "--x     x-------",
+    );
+}
+
+#[test]
+fn test_bitxor() {
+    assert_op!(
+        "  o-----o       ",
+        ^,
+        "     o----o     ",
+        "  o--x  x-o     ",
+    );
+    assert_op!(
+        "---o        o---",
+        ^,
+        "-----o   o------",
+        "   x-o   o--x   ",
+    );
+    assert_op!(
+        "  o-----o       ",
+        ^,
+        "  o-----o       ",
+        "                ",
+    );
+}
+
+#[test]
+fn test_not() {
+    assert_not!("  o-----o       ", "--x     x-------");
+    assert_not!("---o        o---", "   x--------x   ");
+    assert_not!("-----x----------", "     o          ");
+    assert_not!("----------------", "                ");
+    assert_not!("                ", "----------------");
+}
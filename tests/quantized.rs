@@ -0,0 +1,46 @@
+mod common;
+
+use common::wrange;
+use proptest::prelude::*;
+use wrange::Wrange;
+
+/// The number of values two ranges have in common
+fn common_measure(a: &Wrange<u32>, b: &Wrange<u32>) -> u128 {
+    Wrange::intersection(a, b)
+        .normalized()
+        .to_vec()
+        .iter()
+        .map(|r| r.measure())
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn quantize_outward_encloses(w in wrange::<u32>(), power in 0u8..=32) {
+        let q: Wrange<u32> = w.quantize_outward(power).into();
+        prop_assert_eq!(common_measure(&w, &q), w.measure());
+    }
+
+    #[test]
+    fn quantize_inward_is_enclosed(w in wrange::<u32>(), power in 0u8..=32) {
+        let q: Wrange<u32> = w.quantize_inward(power).into();
+        prop_assert_eq!(common_measure(&w, &q), q.measure());
+    }
+
+    #[test]
+    fn quantize_is_idempotent(w in wrange::<u32>(), power in 0u8..=32) {
+        let outward = w.quantize_outward(power);
+        let inward = w.quantize_inward(power);
+        prop_assert_eq!(Wrange::from(outward).quantize_outward(power), outward);
+        prop_assert_eq!(Wrange::from(outward).quantize_inward(power), outward);
+        prop_assert_eq!(Wrange::from(inward).quantize_outward(power), inward);
+        prop_assert_eq!(Wrange::from(inward).quantize_inward(power), inward);
+    }
+
+    #[test]
+    fn quantize_at_power_zero_is_exact(w in wrange::<u32>()) {
+        let q: Wrange<u32> = w.quantize_outward(0).into();
+        prop_assert_eq!(q.measure(), w.measure());
+        prop_assert_eq!(common_measure(&w, &q), w.measure());
+    }
+}
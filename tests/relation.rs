@@ -0,0 +1,109 @@
+use std::collections::BTreeSet;
+
+use wrange::{Bound, Bounds, Relation, Wrange, WrangeSet};
+
+/// Every well-formed, normalized Wrange whose endpoints lie on even positions in 2..=12.
+/// The odd positions in between stand in for the points strictly between
+/// two endpoints, so that checking membership of every u8 gives an exact
+/// picture of the coverage of each range.
+fn all_wranges() -> Vec<Wrange<u8>> {
+    use Bound::*;
+    let bounds: Vec<Bound<u8>> = (2..=12)
+        .step_by(2)
+        .flat_map(|x| vec![Inclusive(x), Exclusive(x)])
+        .collect();
+    let mut wranges = vec![Wrange::Empty, Wrange::Full];
+    for a in bounds.iter() {
+        for b in bounds.iter() {
+            wranges.push(Wrange::new(*a, *b));
+        }
+        wranges.push(Wrange::Divergent(Bounds(*a, *a)));
+    }
+    let mut normalized: Vec<_> = wranges.into_iter().map(Wrange::normalized).collect();
+    normalized.dedup();
+    normalized
+}
+
+fn contains(w: &Wrange<u8>, x: u8) -> bool {
+    use Bound::*;
+    let above = |b: &Bound<u8>| match b {
+        Inclusive(b) => x >= *b,
+        Exclusive(b) => x > *b,
+    };
+    let below = |b: &Bound<u8>| match b {
+        Inclusive(b) => x <= *b,
+        Exclusive(b) => x < *b,
+    };
+    match w.normalized() {
+        Wrange::Empty => false,
+        Wrange::Full => true,
+        Wrange::Convergent(Bounds(a, b)) => above(&a) && below(&b),
+        Wrange::Divergent(Bounds(a, b)) => above(&a) || below(&b),
+    }
+}
+
+fn members(s: &WrangeSet<u8>) -> BTreeSet<u8> {
+    let wranges = s.to_vec();
+    (0..=255)
+        .filter(|x| wranges.iter().any(|w| contains(w, *x)))
+        .collect()
+}
+
+#[test]
+fn test_predicates_agree_with_intersection() {
+    let wranges = all_wranges();
+    for a in wranges.iter() {
+        let a_members = members(&WrangeSet::from(*a));
+        for b in wranges.iter() {
+            let b_members = members(&WrangeSet::from(*b));
+            let both = members(&Wrange::intersection(a, b));
+
+            assert_eq!(a.overlaps(b), !both.is_empty(), "{:?} {:?}", a, b);
+            assert_eq!(a.is_disjoint(b), both.is_empty(), "{:?} {:?}", a, b);
+            assert_eq!(a.is_subset_of(b), both == a_members, "{:?} {:?}", a, b);
+            assert_eq!(a.is_superset_of(b), both == b_members, "{:?} {:?}", a, b);
+
+            let relation = a.relation(b);
+            assert_eq!(
+                relation == Relation::Equal,
+                a_members == b_members,
+                "{:?} {:?}",
+                a,
+                b
+            );
+            if a.touches(b) {
+                assert!(both.is_empty());
+                assert!(matches!(
+                    relation,
+                    Relation::Meets | Relation::MetBy | Relation::MeetsAndMetBy
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_relation_is_symmetric() {
+    use Relation::*;
+
+    let converse = |r| match r {
+        Meets => MetBy,
+        MetBy => Meets,
+        Overlaps => OverlappedBy,
+        OverlappedBy => Overlaps,
+        Starts => StartedBy,
+        StartedBy => Starts,
+        Finishes => FinishedBy,
+        FinishedBy => Finishes,
+        During => Contains,
+        Contains => During,
+        r => r,
+    };
+
+    let wranges = all_wranges();
+    for a in wranges.iter() {
+        for b in wranges.iter() {
+            assert_eq!(a.relation(b), converse(b.relation(a)), "{:?} {:?}", a, b);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use common::wrange;
+use proptest::prelude::*;
+use wrange::Wrange;
+
+proptest! {
+    #[test]
+    fn expand_never_loses_values_u8(w in wrange::<u8>(), n in 0u128..300) {
+        prop_assume!(w.normalized() != Wrange::Empty);
+        prop_assert!(w.expand(n).measure() >= w.measure());
+    }
+
+    #[test]
+    fn expand_grows_by_both_ends_u8(w in wrange::<u8>(), n in 0u128..300) {
+        prop_assume!(w.measure() > 0);
+        prop_assert_eq!(w.expand(n).measure(), (w.measure() + 2 * n).min(256));
+    }
+
+    #[test]
+    fn expand_never_loses_values_i16(w in wrange::<i16>(), n: u16) {
+        prop_assume!(w.normalized() != Wrange::Empty);
+        let n = n as u128;
+        prop_assert!(w.expand(n).measure() >= w.measure());
+    }
+}
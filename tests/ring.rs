@@ -0,0 +1,55 @@
+use proptest::prelude::*;
+use wrange::ring::HashRing;
+use wrange::WrangeSet;
+
+fn contains(set: &WrangeSet<u8>, key: u8) -> bool {
+    set.to_vec().iter().any(|r| r.contains(&key))
+}
+
+fn ring() -> impl Strategy<Value = HashRing<u8, u8>> {
+    prop::collection::vec((any::<u8>(), 0u8..4), 1..10).prop_map(|tokens| {
+        let mut ring = HashRing::new();
+        for (t, n) in tokens {
+            ring.insert(n, vec![t]);
+        }
+        ring
+    })
+}
+
+proptest! {
+    #[test]
+    fn ownership_agrees_with_owners_of(ring in ring(), replicas in 1usize..4) {
+        for node in ring.nodes() {
+            let owned = ring.ownership(node, replicas);
+            for key in 0..=255 {
+                prop_assert_eq!(
+                    contains(&owned, key),
+                    ring.owners_of(&key, replicas).contains(node)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn moves_agree_with_owners_of(
+        ring in ring(),
+        tokens in prop::collection::vec(any::<u8>(), 1..4),
+        replicas in 1usize..4,
+    ) {
+        let mut after = ring.clone();
+        after.insert(9, tokens.clone());
+        let moves = ring.moves_on_join(9, tokens, replicas);
+        for key in 0..=255 {
+            let old = ring.owners_of(&key, replicas);
+            let new = after.owners_of(&key, replicas);
+            for node in 0..10 {
+                let (gained, lost) = match moves.get(&node) {
+                    Some(m) => (contains(&m.gained, key), contains(&m.lost, key)),
+                    None => (false, false),
+                };
+                prop_assert_eq!(gained, new.contains(&node) && !old.contains(&node));
+                prop_assert_eq!(lost, old.contains(&node) && !new.contains(&node));
+            }
+        }
+    }
+}
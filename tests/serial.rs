@@ -0,0 +1,40 @@
+use proptest::prelude::*;
+use wrange::{Bound, SerialWrange};
+
+fn window() -> impl Strategy<Value = SerialWrange<u16>> {
+    (any::<u16>(), 0u16..32768).prop_map(|(start, len)| {
+        SerialWrange::new(
+            Bound::Inclusive(start),
+            Bound::Inclusive(start.wrapping_add(len)),
+        )
+        .unwrap()
+    })
+}
+
+proptest! {
+    #[test]
+    fn contains_follows_serial_order(w in window(), x in any::<u16>()) {
+        let (start, end) = match (w.start(), w.end()) {
+            (Bound::Inclusive(a), Bound::Inclusive(b)) => (*a, *b),
+            _ => unreachable!(),
+        };
+        let expected = x.wrapping_sub(start) <= end.wrapping_sub(start);
+        prop_assert_eq!(w.contains(&x), expected);
+    }
+
+    #[test]
+    fn intersection_agrees_with_contains(a in window(), b in window(), x in any::<u16>()) {
+        let both = SerialWrange::intersection(&a, &b);
+        prop_assert_eq!(
+            both.map_or(false, |w| w.contains(&x)),
+            a.contains(&x) && b.contains(&x)
+        );
+    }
+
+    #[test]
+    fn advance_preserves_contains(mut w in window(), x in any::<u16>(), n in any::<u16>()) {
+        let before = w.contains(&x);
+        w.advance(n);
+        prop_assert_eq!(w.contains(&x.wrapping_add(n)), before);
+    }
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::wrange;
+use proptest::prelude::*;
+use wrange::WrangeSet;
+
+proptest! {
+    #[test]
+    fn shift_preserves_measure_u8(w in wrange::<u8>(), d: u8) {
+        prop_assert_eq!(w.shift_by(d).measure(), w.measure());
+    }
+
+    #[test]
+    fn shift_preserves_measure_i32(w in wrange::<i32>(), d: i32) {
+        prop_assert_eq!(w.shift_by(d).measure(), w.measure());
+    }
+
+    #[test]
+    fn shift_preserves_measure_u64(w in wrange::<u64>(), d: u64) {
+        prop_assert_eq!(w.shift_by(d).measure(), w.measure());
+    }
+
+    #[test]
+    fn shift_is_undone_by_negation_u8(w in wrange::<u8>(), d: u8) {
+        prop_assert_eq!(w.shift_by(d).shift_by(d.wrapping_neg()), w);
+    }
+
+    #[test]
+    fn shift_is_undone_by_negation_i16(w in wrange::<i16>(), d: i16) {
+        prop_assert_eq!(w.shift_by(d).shift_by(d.wrapping_neg()), w);
+    }
+
+    #[test]
+    fn shifts_compose_u8(w in wrange::<u8>(), d: u8, e: u8) {
+        prop_assert_eq!(w.shift_by(d).shift_by(e), w.shift_by(d.wrapping_add(e)));
+    }
+
+    #[test]
+    fn rotate_matches_shift_u32(w in wrange::<u32>(), d: u32) {
+        let mut r = w;
+        r.rotate(d);
+        prop_assert_eq!(r, w.shift_by(d));
+    }
+
+    #[test]
+    fn set_shift_is_undone_by_negation_u8(ws in prop::collection::vec(wrange::<u8>(), 0..8), d: u8) {
+        let set: WrangeSet<u8> = ws.into();
+        prop_assert_eq!(set.shift_by(d).shift_by(d.wrapping_neg()), set);
+    }
+}
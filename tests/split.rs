@@ -0,0 +1,66 @@
+mod common;
+
+use common::wrange;
+use proptest::prelude::*;
+use wrange::Wrange;
+
+/// Check that the pieces contain exactly the values of the original range,
+/// with no value contained in more than one piece
+fn assert_tiles(original: &Wrange<u8>, pieces: &[Wrange<u8>]) -> Result<(), TestCaseError> {
+    for x in 0..=255u8 {
+        let count = pieces.iter().filter(|p| p.contains(&x)).count();
+        prop_assert_eq!(count, original.contains(&x) as usize, "value {}", x);
+    }
+    for (i, a) in pieces.iter().enumerate() {
+        for b in pieces[i + 1..].iter() {
+            let overlap: u128 = Wrange::intersection(a, b)
+                .to_vec()
+                .iter()
+                .map(|r| r.measure())
+                .sum();
+            prop_assert_eq!(overlap, 0, "{:?} {:?}", a, b);
+        }
+    }
+    prop_assert_eq!(
+        pieces.iter().map(|p| p.measure()).sum::<u128>(),
+        original.measure()
+    );
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn split_into_tiles(w in wrange::<u8>(), n in 1usize..20) {
+        let pieces = w.split_into(n);
+        prop_assert_eq!(pieces.len(), n);
+        assert_tiles(&w, &pieces)?;
+    }
+
+    #[test]
+    fn split_into_is_balanced(w in wrange::<u8>(), n in 1usize..20) {
+        let measures: Vec<u128> = w.split_into(n).iter().map(|p| p.measure()).collect();
+        let min = measures.iter().min().unwrap();
+        let max = measures.iter().max().unwrap();
+        prop_assert!(max - min <= 1, "{:?}", measures);
+    }
+
+    #[test]
+    fn split_weighted_tiles(w in wrange::<u8>(), weights in prop::collection::vec(0u32..5, 1..8)) {
+        prop_assume!(weights.iter().any(|w| *w > 0));
+        assert_tiles(&w, &w.split_weighted(&weights))?;
+    }
+
+    #[test]
+    fn split_at_tiles(w in wrange::<u8>(), x: u8) {
+        assert_tiles(&w, &w.split_at(&x))?;
+    }
+
+    #[test]
+    fn split_at_contained_point_starts_second_piece(w in wrange::<u8>(), x: u8) {
+        let pieces = w.split_at(&x);
+        if pieces.len() == 2 {
+            prop_assert!(pieces[1].contains(&x));
+            prop_assert!(!pieces[0].contains(&x));
+        }
+    }
+}
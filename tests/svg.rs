@@ -0,0 +1,82 @@
+//! Snapshot tests of the SVG diagrams. Run with `UPDATE_SNAPSHOTS=1` to
+//! write the current output over the snapshots in `tests/snapshots/svg`,
+//! then check the changes by eye.
+
+use std::path::PathBuf;
+use wrange::ascii::ascii;
+use wrange::svg::{render, render_with};
+use wrange::{Angle, Bound, Bounds, Wrange, WrangeSet};
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", "svg"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{}.svg", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("no snapshot at {}: {}", path.display(), e));
+    assert_eq!(actual, expected, "snapshot {} differs", name);
+}
+
+/// A set of u8 ranges drawn in ASCII art of at most 64 columns, stretched
+/// to four values per column so that the art spans the whole domain
+fn stretched(s: &str) -> WrangeSet<u8> {
+    ascii(s)
+        .iter()
+        .map(|w| match *w {
+            Wrange::Convergent(Bounds(a, b)) => Wrange::Convergent(Bounds(scale(a), scale(b))),
+            Wrange::Divergent(Bounds(a, b)) => Wrange::Divergent(Bounds(scale(a), scale(b))),
+            w => w,
+        })
+        .collect()
+}
+
+fn scale(b: Bound<u8>) -> Bound<u8> {
+    match b {
+        Bound::Inclusive(x) => Bound::Inclusive(x * 4),
+        Bound::Exclusive(x) => Bound::Exclusive(x * 4),
+    }
+}
+
+#[test]
+fn test_variants() {
+    use Bound::*;
+    let sets: [WrangeSet<u8>; 6] = [
+        Wrange::Empty.into(),
+        Wrange::Full.into(),
+        Wrange::new(Inclusive(16), Exclusive(96)).into(),
+        Wrange::new(Exclusive(224), Inclusive(32)).into(),
+        Wrange::new_inclusive(128, 128).into(),
+        Wrange::Divergent(Bounds(Exclusive(64), Exclusive(64))).into(),
+    ];
+    assert_snapshot("variants", &render(&sets));
+}
+
+#[test]
+fn test_sets() {
+    let sets = [
+        stretched("--]   [-----)  (---]    *      [------------]          [-----"),
+        stretched("  (----)          [------------------]     *                 "),
+    ];
+    let union = &sets[0] | &sets[1];
+    let rings = [sets[0].clone(), sets[1].clone(), union];
+    assert_snapshot("sets", &render(&rings));
+}
+
+#[test]
+fn test_angles() {
+    let deg = Angle::<f64>::degrees();
+    let sets = [
+        WrangeSet::from(deg.sector(350.0, 10.0)),
+        WrangeSet::from(vec![deg.sector(90.0, 180.0), deg.sector(200.0, 270.0)]),
+    ];
+    let svg = render_with(&sets, |x| x.0 / deg.period());
+    assert_snapshot("angles", &svg);
+}
+
+#[test]
+fn test_empty() {
+    assert_snapshot("none", &render::<u8>(&[]));
+}
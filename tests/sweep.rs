@@ -0,0 +1,59 @@
+mod common;
+
+use common::wrange;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use wrange::{Wrange, WrangeSet};
+
+fn sets() -> impl Strategy<Value = Vec<Vec<Wrange<u8>>>> {
+    vec(vec(wrange::<u8>(), 0..4), 0..6)
+}
+
+fn contains(set: &WrangeSet<u8>, x: u8) -> bool {
+    set.to_vec().iter().any(|r| r.contains(&x))
+}
+
+proptest! {
+    #[test]
+    fn intersection_all_matches_fold(sets in sets()) {
+        let all = WrangeSet::intersection_all(sets.clone());
+        let folded = sets
+            .into_iter()
+            .map(WrangeSet::from)
+            .fold(Wrange::Full.into(), |a, b| WrangeSet::intersection(&a, &b));
+        prop_assert_eq!(all.to_canonical_vec(), folded.to_canonical_vec());
+        for x in 0..=255 {
+            prop_assert_eq!(contains(&all, x), contains(&folded, x));
+        }
+    }
+
+    #[test]
+    fn union_all_matches_fold(sets in sets()) {
+        let all = WrangeSet::union_all(sets.clone());
+        let folded = sets
+            .into_iter()
+            .map(WrangeSet::from)
+            .fold(WrangeSet::from(vec![]), |a, b| WrangeSet::union(&a, &b));
+        prop_assert_eq!(all, folded.canonicalized());
+    }
+}
+
+proptest! {
+    #[test]
+    fn operators_match_membership(a in vec(wrange::<u8>(), 0..4), b in vec(wrange::<u8>(), 0..4)) {
+        let (a, b) = (WrangeSet::from(a), WrangeSet::from(b));
+        let union = a.clone() | b.clone();
+        let intersection = a.clone() & b.clone();
+        let difference = a.clone() - b.clone();
+        let symmetric = a.clone() ^ b.clone();
+        let complement = !a.clone();
+        for x in 0..=255 {
+            let (in_a, in_b) = (contains(&a, x), contains(&b, x));
+            prop_assert_eq!(contains(&union, x), in_a || in_b);
+            prop_assert_eq!(contains(&intersection, x), in_a && in_b);
+            prop_assert_eq!(contains(&difference, x), in_a && !in_b);
+            prop_assert_eq!(contains(&symmetric, x), in_a != in_b);
+            prop_assert_eq!(contains(&complement, x), !in_a);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+mod common;
+use common::{bound, wrange};
+
+use proptest::prelude::*;
+use wrange::{Bound, Wrange, WrangeSet};
+
+proptest! {
+    #[test]
+    fn bound_round_trips(b in bound::<i64>()) {
+        prop_assert_eq!(b.to_string().parse::<Bound<i64>>(), Ok(b));
+    }
+
+    #[test]
+    fn wrange_round_trips(w in wrange::<i16>()) {
+        prop_assert_eq!(w.to_string().parse::<Wrange<i16>>(), Ok(w));
+    }
+
+    #[test]
+    fn wrange_set_round_trips(ws in prop::collection::vec(wrange::<u8>(), 0..6)) {
+        let set = WrangeSet::from(ws);
+        prop_assert_eq!(set.to_string().parse::<WrangeSet<u8>>(), Ok(set));
+    }
+}
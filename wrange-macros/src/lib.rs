@@ -0,0 +1,99 @@
+//! Procedural macros for the `wrange` crate.
+//!
+//! [`ascii!`] reads the same ASCII art as `wrange::ascii::ascii`, but at
+//! compile time, so that a malformed test fixture fails the build rather
+//! than the test.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+use wrange::ascii::{try_ascii, AsciiError};
+use wrange::{Bound, Bounds, Wrange};
+
+/// Read ASCII art as a `WrangeSet<u8>` at compile time.
+///
+/// Takes a single string literal, drawn as for `wrange::ascii::ascii`, and
+/// expands to the construction of the same set out of constant ranges.
+/// Malformed art is a compile error, which names the offending column and
+/// marks it with a caret under a copy of the art.
+///
+/// On stable Rust the error's span covers the whole literal, since no stable
+/// API can point into a literal. With a nightly compiler and
+/// `--cfg procmacro2_semver_exempt`, the span narrows to the offending
+/// columns.
+///
+/// The expansion refers to the `wrange` crate by name, so it must be a
+/// dependency of the calling crate.
+#[proc_macro]
+pub fn ascii(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    let art = lit.value();
+    match try_ascii(&art) {
+        Ok(set) => {
+            let ranges = set.iter().map(wrange);
+            quote! {
+                <::wrange::WrangeSet<u8> as ::core::iter::FromIterator<_>>::from_iter([
+                    #(#ranges,)*
+                ])
+            }
+            .into()
+        }
+        Err(e) => error(&lit, &art, e).to_compile_error().into(),
+    }
+}
+
+fn bound(b: &Bound<u8>) -> TokenStream2 {
+    match b {
+        Bound::Inclusive(x) => quote!(::wrange::Bound::Inclusive(#x)),
+        Bound::Exclusive(x) => quote!(::wrange::Bound::Exclusive(#x)),
+    }
+}
+
+fn wrange(w: &Wrange<u8>) -> TokenStream2 {
+    match w {
+        Wrange::Empty => quote!(::wrange::Wrange::Empty),
+        Wrange::Full => quote!(::wrange::Wrange::Full),
+        Wrange::Convergent(Bounds(a, b)) => {
+            let (a, b) = (bound(a), bound(b));
+            quote!(::wrange::Wrange::Convergent(::wrange::Bounds(#a, #b)))
+        }
+        Wrange::Divergent(Bounds(a, b)) => {
+            let (a, b) = (bound(a), bound(b));
+            quote!(::wrange::Wrange::Divergent(::wrange::Bounds(#a, #b)))
+        }
+    }
+}
+
+/// The error for malformed art, marking the columns at fault under it.
+///
+/// The span covers just those columns only where `Literal::subspan` works,
+/// which takes nightly and `--cfg procmacro2_semver_exempt`. Everywhere else
+/// it covers the whole literal, and only the caret line marks the column.
+fn error(lit: &LitStr, art: &str, e: AsciiError) -> syn::Error {
+    let columns = match e {
+        AsciiError::InvalidChar(i) => {
+            let len = art[i..].chars().next().map_or(1, char::len_utf8);
+            Some((i, i + len))
+        }
+        AsciiError::Unpaired(i)
+        | AsciiError::StrayJoin(i)
+        | AsciiError::Misplaced(i)
+        | AsciiError::ExtraBoundary(i) => Some((i, i + 1)),
+        AsciiError::MixedStretch { begin, end } => Some((begin, end)),
+        AsciiError::TooLong(_) | AsciiError::MismatchedWrap => None,
+    };
+    let mut message = format!("Malformed ascii. {}", e);
+    let mut span = lit.span();
+    if let Some((begin, end)) = columns {
+        let pad = art[..begin].chars().count();
+        let width = art[begin..end].chars().count().max(1);
+        message += &format!("\n |{}|\n  {}{}", art, " ".repeat(pad), "^".repeat(width));
+        // byte offsets into the value only line up with the source when the
+        // literal is written plainly, without escapes or raw delimiters
+        if lit.token().to_string() == format!("{:?}", art) {
+            span = lit.token().subspan(begin + 1..end + 1).unwrap_or(span);
+        }
+    }
+    syn::Error::new(span, message)
+}
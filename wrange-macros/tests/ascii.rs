@@ -0,0 +1,24 @@
+use wrange::ascii::ascii as runtime;
+use wrange_macros::ascii;
+
+#[test]
+fn test_matches_runtime() {
+    assert_eq!(ascii!("  o-----o       "), runtime("  o-----o       "));
+    assert_eq!(ascii!("--x---x-"), runtime("--x---x-"));
+    assert_eq!(ascii!("x------x"), runtime("x------x"));
+    assert_eq!(ascii!("-------x"), runtime("-------x"));
+    assert_eq!(ascii!("x x x x "), runtime("x x x x "));
+    assert_eq!(ascii!("--O  X--"), runtime("--O  X--"));
+    assert_eq!(ascii!("--------"), runtime("--------"));
+    assert_eq!(ascii!("        "), runtime("        "));
+    assert_eq!(ascii!(""), runtime(""));
+    assert_eq!(ascii!("--):(---"), runtime("--):(---"));
+    assert_eq!(ascii!("  [--|--]  "), runtime("  [--|--]  "));
+    assert_eq!(ascii!("[-)(--] * "), runtime("[-)(--] * "));
+}
+
+#[test]
+fn test_ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
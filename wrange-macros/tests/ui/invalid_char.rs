@@ -0,0 +1,5 @@
+use wrange_macros::ascii;
+
+fn main() {
+    let _ = ascii!("  o--a--o  ");
+}